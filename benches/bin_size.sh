@@ -0,0 +1,18 @@
+#!/bin/sh
+# Compares the size of stripped release binaries that only differ in whether the same types derive
+# `Debug` with `SmartDebug` or `#[derive(Debug)]`. Attr-free types get the same code as std's
+# derive, so the sizes should be about the same unless the `depth` feature is passed along e.g.
+#
+#     benches/bin_size.sh --features depth
+
+set -eu
+
+cd "$(dirname "$0")/.."
+cargo build --release --config profile.release.strip=true --example size_std --example size_smart "$@"
+
+examples="${CARGO_TARGET_DIR:-target}/release/examples"
+std_size=$(wc -c < "$examples/size_std")
+smart_size=$(wc -c < "$examples/size_smart")
+echo "#[derive(Debug)]: $std_size bytes"
+echo "SmartDebug:       $smart_size bytes"
+echo "Difference:       $((smart_size - std_size)) bytes"
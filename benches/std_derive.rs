@@ -0,0 +1,52 @@
+//! Compares the formatting throughput of `SmartDebug` against `#[derive(Debug)]`
+//!
+//! Types without any attrs should be just as fast as the std derive since they get the same code.
+//! The attributed types show what the runtime skip checks cost on top of that. Run with
+//! `--features depth` to see what tracking the depth costs
+
+use std::fmt::Write;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use smart_debug::SmartDebug;
+
+#[macro_use]
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+both! {
+    pub struct Point { pub x: f32, pub y: f32, pub z: f32 }
+    pub struct Packet(pub u16, pub &'static str, pub [u8; 4]);
+}
+
+#[derive(SmartDebug, Default)]
+#[debug(skip_defaults)]
+struct SkipDefaults {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+fn bench_fmt(c: &mut Criterion, name: &str, value: &dyn std::fmt::Debug) {
+    let mut buf = String::with_capacity(256);
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            buf.clear();
+            write!(buf, "{:?}", black_box(value)).unwrap();
+        })
+    });
+}
+
+fn named(c: &mut Criterion) {
+    let (x, y, z) = (1.0, 0.0, 3.0);
+    bench_fmt(c, "named/std", &std_derive::Point { x, y, z });
+    bench_fmt(c, "named/smart", &smart::Point { x, y, z });
+    bench_fmt(c, "named/smart_skip_defaults", &SkipDefaults { x, y, z });
+}
+
+fn tuple(c: &mut Criterion) {
+    bench_fmt(c, "tuple/std", &std_derive::Packet(7, "ping", [1, 2, 3, 4]));
+    bench_fmt(c, "tuple/smart", &smart::Packet(7, "ping", [1, 2, 3, 4]));
+}
+
+criterion_group!(benches, named, tuple);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+/// Derives `Debug` for a handful of attr-free types with `$derive` along with a `main()` that
+/// formats each of them
+///
+/// `benches/bin_size.sh` compares the release binaries built from this with `SmartDebug` and
+/// `#[derive(Debug)]`
+macro_rules! sized_types {
+    ($derive:path) => {
+        // Fields only read by the derived `Debug` impl count as dead code
+        #[allow(dead_code)]
+        mod types {
+            #[derive($derive)]
+            pub struct Point {
+                pub x: f32,
+                pub y: f32,
+                pub z: f32,
+            }
+
+            #[derive($derive)]
+            pub struct Packet(pub u16, pub &'static str, pub [u8; 4]);
+
+            #[derive($derive)]
+            pub struct Header {
+                pub version: u8,
+                pub flags: u16,
+                pub len: u32,
+                pub name: String,
+            }
+
+            #[derive($derive)]
+            pub struct Frame<T> {
+                pub header: Header,
+                pub payload: Vec<T>,
+                pub checksum: Option<u32>,
+            }
+
+            #[derive($derive)]
+            pub struct Wrapper(pub Frame<Point>);
+
+            #[derive($derive)]
+            pub struct Unit;
+        }
+
+        fn main() {
+            use types::*;
+
+            // Keeps the values from being known at compile time
+            let n = std::env::args().count() as u8;
+            let point = Point {
+                x: f32::from(n),
+                y: 0.0,
+                z: 1.5,
+            };
+            let frame = Frame {
+                header: Header {
+                    version: n,
+                    flags: u16::from(n) << 4,
+                    len: 3,
+                    name: n.to_string(),
+                },
+                payload: vec![point],
+                checksum: Some(u32::from(n)),
+            };
+
+            println!("{:?}", Packet(u16::from(n), "ping", [n; 4]));
+            println!("{:#?}", Wrapper(frame));
+            println!("{:?}", Unit);
+        }
+    };
+}
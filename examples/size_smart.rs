@@ -0,0 +1,7 @@
+//! `sized_types!` derived with `SmartDebug`
+
+#[macro_use]
+#[path = "size/types.rs"]
+mod size;
+
+sized_types!(smart_debug::SmartDebug);
@@ -0,0 +1,7 @@
+//! `sized_types!` derived with `#[derive(Debug)]`
+
+#[macro_use]
+#[path = "size/types.rs"]
+mod size;
+
+sized_types!(Debug);
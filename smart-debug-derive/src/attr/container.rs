@@ -1,10 +1,11 @@
-use std::iter::FromIterator;
+use crate::attr::View;
 
 use syn::{
+    ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    Attribute, Expr, Ident, LitStr, Token,
+    Attribute, Expr, ExprLit, Ident, Lit, LitStr, Path, Token,
 };
 
 #[derive(Clone, Debug)]
@@ -13,16 +14,103 @@ pub enum Skip {
     Defaults,
 }
 
+/// What gets displayed in place of a struct's hidden fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Marker {
+    /// Nothing at all
+    None,
+    /// `..`
+    Dots,
+    /// `.. (2 hidden)`
+    Count,
+    /// `.. (skipped: cache, handle)`
+    Names,
+}
+
+impl Marker {
+    fn parse(value: &AttrValue) -> Option<Self> {
+        let marker = match value {
+            AttrValue::Expr(Expr::Lit(ExprLit {
+                lit: Lit::Bool(lit),
+                ..
+            })) => {
+                if lit.value {
+                    Self::Dots
+                } else {
+                    Self::None
+                }
+            }
+            AttrValue::Expr(Expr::Path(path)) => {
+                match path.path.get_ident()?.to_string().as_str() {
+                    "count" => Self::Count,
+                    "names" => Self::Names,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(marker)
+    }
+}
+
+/// How a tuple struct's skipped fields get displayed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TupleSkip {
+    /// `Color(_, _, _, 255)`
+    Placeholder,
+    /// `Color(255, ..)`
+    Omit,
+    /// Like `Placeholder`, but skipped fields at the end are left off entirely
+    Trailing,
+}
+
+impl TupleSkip {
+    fn parse(value: &AttrValue) -> Option<Self> {
+        let AttrValue::Expr(Expr::Path(path)) = value else {
+            return None;
+        };
+
+        let tuple_skip = match path.path.get_ident()?.to_string().as_str() {
+            "placeholder" => Self::Placeholder,
+            "omit" => Self::Omit,
+            "trailing" => Self::Trailing,
+            _ => return None,
+        };
+
+        Some(tuple_skip)
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Attrs {
     pub bare: Option<LitStr>,
     pub skip: Option<Skip>,
+    pub inline_fields: bool,
+    pub marker: Option<Marker>,
+    /// What's displayed for skipped tuple fields instead of `_`
+    pub placeholder: Option<LitStr>,
+    pub tuple_skip: Option<TupleSkip>,
+    /// Display a tuple struct like a struct with each field's index as its name
+    pub indexed: bool,
+    /// Where the `smart_debug` crate is found for the generated code e.g. when it's re-exported
+    pub crate_path: Option<Path>,
+    /// Display values that are already being formatted as a `<cycle: ...>` marker instead. Cycles
+    /// are tracked in a thread-local, so this does nothing when the runtime crate lacks `std`
+    pub cycle_safe: bool,
+    /// How many levels of nested `SmartDebug` values get expanded with the container as the first.
+    /// Depth is only tracked with the runtime crate's `depth` feature, so this does nothing without
+    /// it
+    pub max_depth: Option<Expr>,
+    /// Overrides for the container's attrs within each named view
+    pub views: Vec<View<Attr>>,
 }
 
 impl Attrs {
-    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+    /// Parses all of the attrs within the `namespace` e.g. `debug` for `#[debug(...)]`
+    pub fn parse(attrs: &[Attribute], namespace: &str) -> syn::Result<Self> {
         let mut parsed = Vec::new();
-        for attr in attrs.iter().filter(|attr| attr.path().is_ident("debug")) {
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident(namespace)) {
             for attr in attr.parse_args_with(Punctuated::<Attr, Token![,]>::parse_terminated)? {
                 parsed.push(attr);
             }
@@ -30,6 +118,31 @@ impl Attrs {
 
         Self::try_from(parsed)
     }
+
+    /// The attrs that apply within the named `view`. Everything that's set for the view takes
+    /// precedence over the regular attr of the same kind
+    pub fn in_view(self, view: Option<&Path>) -> syn::Result<Self> {
+        let Some(view) = view else {
+            return Ok(self);
+        };
+
+        let overrides = Self::try_from(View::overrides(&self.views, view))?;
+        assert!(overrides.views.is_empty(), "views can't be nested");
+
+        Ok(Self {
+            bare: overrides.bare.or(self.bare),
+            skip: overrides.skip.or(self.skip),
+            inline_fields: overrides.inline_fields || self.inline_fields,
+            marker: overrides.marker.or(self.marker),
+            placeholder: overrides.placeholder.or(self.placeholder),
+            tuple_skip: overrides.tuple_skip.or(self.tuple_skip),
+            indexed: overrides.indexed || self.indexed,
+            crate_path: overrides.crate_path.or(self.crate_path),
+            cycle_safe: overrides.cycle_safe || self.cycle_safe,
+            max_depth: overrides.max_depth.or(self.max_depth),
+            views: Vec::new(),
+        })
+    }
 }
 
 impl TryFrom<Vec<Attr>> for Attrs {
@@ -48,6 +161,26 @@ impl TryFrom<Vec<Attr>> for Attrs {
                 | AttrName::Valueless(ValuelessName::SkipDefaults) => {
                     assert!(attrs.skip.is_none());
                 }
+                AttrName::Valueless(ValuelessName::InlineFields) => {
+                    assert!(!attrs.inline_fields);
+                }
+                AttrName::Valueless(ValuelessName::Indexed) => assert!(!attrs.indexed),
+                AttrName::Valueless(ValuelessName::CycleSafe) => assert!(!attrs.cycle_safe),
+                AttrName::Valuefull(ValuefullName::Crate) => assert!(attrs.crate_path.is_none()),
+                AttrName::Valuefull(ValuefullName::ExhaustiveMarker) => {
+                    assert!(attrs.marker.is_none());
+                }
+                AttrName::Valuefull(ValuefullName::MaxDepth) => {
+                    assert!(attrs.max_depth.is_none());
+                }
+                AttrName::Valuefull(ValuefullName::Placeholder) => {
+                    assert!(attrs.placeholder.is_none());
+                }
+                AttrName::Valuefull(ValuefullName::TupleSkip) => {
+                    assert!(attrs.tuple_skip.is_none());
+                }
+                // Each view gets validated separately when it's used
+                AttrName::Valuefull(ValuefullName::View) => {}
             }
 
             // Parse
@@ -61,11 +194,62 @@ impl TryFrom<Vec<Attr>> for Attrs {
                             };
                             attrs.bare = Some(lit);
                         }
+                        ValuefullName::Crate => {
+                            let AttrValue::Expr(Expr::Path(path)) = value else {
+                                panic!("`crate` must be a path");
+                            };
+                            attrs.crate_path = Some(path.path);
+                        }
+                        ValuefullName::ExhaustiveMarker => {
+                            let marker = Marker::parse(&value).expect(
+                                "`exhaustive_marker` must be one of `true`, `false`, `count`, or `names`",
+                            );
+                            attrs.marker = Some(marker);
+                        }
+                        ValuefullName::MaxDepth => {
+                            let AttrValue::Expr(expr) = value else {
+                                panic!("`max_depth` must be a number");
+                            };
+                            // The container is always the first level, so it can't be collapsed
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Int(int), ..
+                            }) = &expr
+                            {
+                                if int.base10_parse::<usize>()? == 0 {
+                                    return Err(syn::Error::new_spanned(
+                                        int,
+                                        "`max_depth` must be at least 1",
+                                    ));
+                                }
+                            }
+                            attrs.max_depth = Some(expr);
+                        }
+                        ValuefullName::Placeholder => {
+                            let AttrValue::LitStr(lit) = value else {
+                                panic!("`placeholder` must be a string literal");
+                            };
+                            attrs.placeholder = Some(lit);
+                        }
+                        ValuefullName::TupleSkip => {
+                            let tuple_skip = TupleSkip::parse(&value).expect(
+                                "`tuple_skip` must be one of `placeholder`, `omit`, or `trailing`",
+                            );
+                            attrs.tuple_skip = Some(tuple_skip);
+                        }
+                        ValuefullName::View => {
+                            let AttrValue::View(view) = value else {
+                                unreachable!()
+                            };
+                            attrs.views.push(view);
+                        }
                     }
                 }
                 AttrName::Valueless(valueless) => match valueless {
                     ValuelessName::Skip => attrs.skip = Some(Skip::Bare),
                     ValuelessName::SkipDefaults => attrs.skip = Some(Skip::Defaults),
+                    ValuelessName::InlineFields => attrs.inline_fields = true,
+                    ValuelessName::Indexed => attrs.indexed = true,
+                    ValuelessName::CycleSafe => attrs.cycle_safe = true,
                 },
             }
         }
@@ -89,13 +273,19 @@ impl Parse for Attr {
             });
         }
 
-        let ident: Ident = input.parse()?;
+        // `crate` is a keyword, so it needs to be parsed specially
+        let ident = input.call(Ident::parse_any)?;
         let name = match AttrName::new(ident) {
             Some(name) => name,
             None => todo!(),
         };
 
-        let value = if input.peek(Token![=]) {
+        let value = if let AttrName::Valuefull(ValuefullName::View) = name {
+            // `view(Name, ...)` holds other attrs instead of exprs
+            let nested;
+            parenthesized!(nested in input);
+            Some(AttrValue::View(View::parse_contents(&nested)?))
+        } else if input.peek(Token![=]) {
             // `name = value` attributes.
             // TODO: vv
             let _assign_token = input.parse::<Token![=]>()?; // skip '='
@@ -108,14 +298,6 @@ impl Parse for Attr {
                     Err(_) => panic!("Failed parsing container expr"),
                 }
             }
-        } else if input.peek(syn::token::Paren) {
-            // TODO: are we going to support these?
-            // `name(...)` attributes.
-            let nested;
-            parenthesized!(nested in input);
-
-            let method_args: Punctuated<_, _> = nested.parse_terminated(Expr::parse, Token![,])?;
-            Some(AttrValue::Call(Vec::from_iter(method_args)))
         } else {
             None
         };
@@ -133,10 +315,19 @@ pub enum AttrName {
 #[derive(Clone, Debug)]
 pub enum ValuefullName {
     Bare,
+    Crate,
+    ExhaustiveMarker,
+    MaxDepth,
+    Placeholder,
+    TupleSkip,
+    View,
 }
 
 #[derive(Clone, Debug)]
 pub enum ValuelessName {
+    CycleSafe,
+    Indexed,
+    InlineFields,
     Skip,
     SkipDefaults,
 }
@@ -144,8 +335,17 @@ pub enum ValuelessName {
 impl AttrName {
     fn new(ident: Ident) -> Option<Self> {
         let name = match ident.to_string().as_str() {
+            "crate" => Self::Valuefull(ValuefullName::Crate),
+            "cycle_safe" => Self::Valueless(ValuelessName::CycleSafe),
+            "exhaustive_marker" => Self::Valuefull(ValuefullName::ExhaustiveMarker),
+            "indexed" => Self::Valueless(ValuelessName::Indexed),
+            "inline_fields" => Self::Valueless(ValuelessName::InlineFields),
+            "max_depth" => Self::Valuefull(ValuefullName::MaxDepth),
+            "placeholder" => Self::Valuefull(ValuefullName::Placeholder),
             "skip" => Self::Valueless(ValuelessName::Skip),
             "skip_defaults" => Self::Valueless(ValuelessName::SkipDefaults),
+            "tuple_skip" => Self::Valuefull(ValuefullName::TupleSkip),
+            "view" => Self::Valuefull(ValuefullName::View),
             _ => return None,
         };
 
@@ -158,5 +358,5 @@ impl AttrName {
 pub enum AttrValue {
     LitStr(LitStr),
     Expr(Expr),
-    Call(Vec<Expr>),
+    View(View<Attr>),
 }
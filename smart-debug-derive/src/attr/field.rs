@@ -1,4 +1,4 @@
-use std::iter::FromIterator;
+use crate::attr::View;
 
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
@@ -6,20 +6,87 @@ use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    Attribute, Expr, Ident, LitStr, Token,
+    Attribute, Expr, Ident, LitStr, Path, Token,
 };
 
-// TODO: make bare and wrapper exclusive
 #[derive(Clone, Debug, Default)]
 pub struct Attrs {
-    pub bare_or_wrapper: Option<BareOrWrapper>,
+    pub repr: Option<Repr>,
+    /// Used instead of `repr` when formatting with `{:#?}`. Only `Bare` and `Wrapper` are allowed
+    pub alt_repr: Option<Repr>,
     pub skip: Option<Skip>,
+    pub only: Option<Only>,
+    /// The minimum verbosity that the field is displayed at. The verbosity is always `0` when the
+    /// runtime crate is built without `std`, so the field is always hidden then
+    pub level: Option<AttrValue>,
+    pub inline: bool,
+    pub rename: Option<LitStr>,
+    /// Overrides for the field's attrs within each named view
+    pub views: Vec<View<Attr>>,
 }
 
+/// How the field's value gets displayed. All of these are mutually exclusive
 #[derive(Clone, Debug)]
-pub enum BareOrWrapper {
+pub enum Repr {
     Bare(LitStr),
     Wrapper(AttrValue),
+    Items(Items),
+    Hex,
+    ByteStr,
+    ByteSize,
+    Duration,
+    UnixTime(TimeUnit),
+    FmtTrait(FmtTrait),
+}
+
+/// Format the field using one of the other `std::fmt` traits
+#[derive(Clone, Copy, Debug)]
+pub enum FmtTrait {
+    Display,
+    LowerHex,
+    UpperHex,
+    Binary,
+    Octal,
+    LowerExp,
+    UpperExp,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum TimeUnit {
+    Secs,
+    Millis,
+}
+
+impl TimeUnit {
+    fn parse(value: &AttrValue) -> Option<Self> {
+        let AttrValue::Expr(Expr::Path(path)) = value else {
+            return None;
+        };
+
+        let unit = match path.path.get_ident()?.to_string().as_str() {
+            "secs" => Self::Secs,
+            "millis" => Self::Millis,
+            _ => return None,
+        };
+
+        Some(unit)
+    }
+}
+
+/// Only show some of the collection's items. Missing limits show no items from that end
+#[derive(Clone, Debug, Default)]
+pub struct Items {
+    pub head: Option<AttrValue>,
+    pub tail: Option<AttrValue>,
+}
+
+/// Only display the field when formatting in one of the modes
+#[derive(Clone, Copy, Debug)]
+pub enum Only {
+    /// Only with `{:#?}`
+    Pretty,
+    /// Only with `{:?}`
+    Compact,
 }
 
 #[derive(Clone, Debug)]
@@ -32,9 +99,10 @@ pub enum Skip {
 }
 
 impl Attrs {
-    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+    /// Parses all of the attrs within the `namespace` e.g. `debug` for `#[debug(...)]`
+    pub fn parse(attrs: &[Attribute], namespace: &str) -> syn::Result<Self> {
         let mut parsed = Vec::new();
-        for attr in attrs.iter().filter(|attr| attr.path().is_ident("debug")) {
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident(namespace)) {
             for attr in attr.parse_args_with(Punctuated::<Attr, Token![,]>::parse_terminated)? {
                 parsed.push(attr);
             }
@@ -42,6 +110,38 @@ impl Attrs {
 
         Self::try_from(parsed)
     }
+
+    /// The attrs that apply within the named `view`. Everything that's set for the view takes
+    /// precedence over the regular attr of the same kind
+    pub fn in_view(self, view: Option<&Path>) -> syn::Result<Self> {
+        let Some(view) = view else {
+            return Ok(self);
+        };
+
+        let overrides = Self::try_from(View::overrides(&self.views, view))?;
+        assert!(overrides.views.is_empty(), "views can't be nested");
+
+        Ok(Self {
+            repr: overrides.repr.or(self.repr),
+            alt_repr: overrides.alt_repr.or(self.alt_repr),
+            skip: overrides.skip.or(self.skip),
+            only: overrides.only.or(self.only),
+            level: overrides.level.or(self.level),
+            inline: overrides.inline || self.inline,
+            rename: overrides.rename.or(self.rename),
+            views: Vec::new(),
+        })
+    }
+
+    fn items_mut(&mut self) -> &mut Items {
+        let repr = self
+            .repr
+            .get_or_insert_with(|| Repr::Items(Items::default()));
+        match repr {
+            Repr::Items(items) => items,
+            _ => unreachable!("Validated before parsing"),
+        }
+    }
 }
 
 impl TryFrom<Vec<Attr>> for Attrs {
@@ -53,8 +153,20 @@ impl TryFrom<Vec<Attr>> for Attrs {
         for Attr { name, value } in unstructured {
             // Validate
             match name {
-                AttrName::Valuefull(ValuefullName::Bare) => {
-                    assert!(attrs.bare_or_wrapper.is_none());
+                AttrName::Valuefull(
+                    ValuefullName::Bare
+                    | ValuefullName::Wrapper
+                    | ValuefullName::MaxItems
+                    | ValuefullName::UnixTime,
+                )
+                | AttrName::Valueless(
+                    ValuelessName::Hex
+                    | ValuelessName::Bytes
+                    | ValuelessName::BytesSize
+                    | ValuelessName::Duration
+                    | ValuelessName::FmtTrait(_),
+                ) => {
+                    assert!(attrs.repr.is_none());
                 }
                 AttrName::Valueless(
                     ValuelessName::Skip | ValuelessName::SkipDefault | ValuelessName::NoSkip,
@@ -62,9 +174,28 @@ impl TryFrom<Vec<Attr>> for Attrs {
                 | AttrName::Valuefull(ValuefullName::SkipFn | ValuefullName::SkipIf) => {
                     assert!(attrs.skip.is_none());
                 }
-                AttrName::Valuefull(ValuefullName::Wrapper) => {
-                    assert!(attrs.bare_or_wrapper.is_none());
+                AttrName::Valuefull(ValuefullName::Alt | ValuefullName::AltWrapper) => {
+                    assert!(attrs.alt_repr.is_none());
+                }
+                AttrName::Valueless(ValuelessName::PrettyOnly | ValuelessName::CompactOnly) => {
+                    assert!(attrs.only.is_none());
                 }
+                AttrName::Valueless(ValuelessName::Inline) => assert!(!attrs.inline),
+                AttrName::Valuefull(ValuefullName::Rename) => assert!(attrs.rename.is_none()),
+                AttrName::Valuefull(ValuefullName::Level) => assert!(attrs.level.is_none()),
+                // Each view gets validated separately when it's used
+                AttrName::Valuefull(ValuefullName::View) => {}
+                // `head` and `tail` can be combined with each other, but nothing else
+                AttrName::Valuefull(ValuefullName::Head) => match &attrs.repr {
+                    None => {}
+                    Some(Repr::Items(items)) => assert!(items.head.is_none()),
+                    Some(_) => panic!("`head` can't be combined with other representations"),
+                },
+                AttrName::Valuefull(ValuefullName::Tail) => match &attrs.repr {
+                    None => {}
+                    Some(Repr::Items(items)) => assert!(items.tail.is_none()),
+                    Some(_) => panic!("`tail` can't be combined with other representations"),
+                },
             }
 
             // Parse
@@ -76,12 +207,45 @@ impl TryFrom<Vec<Attr>> for Attrs {
                             let AttrValue::LitStr(lit) = value else {
                                 unreachable!()
                             };
-                            attrs.bare_or_wrapper = Some(BareOrWrapper::Bare(lit));
+                            attrs.repr = Some(Repr::Bare(lit));
                         }
+                        ValuefullName::Alt => {
+                            let AttrValue::LitStr(lit) = value else {
+                                panic!("`alt` must be a string literal");
+                            };
+                            attrs.alt_repr = Some(Repr::Bare(lit));
+                        }
+                        ValuefullName::AltWrapper => attrs.alt_repr = Some(Repr::Wrapper(value)),
                         ValuefullName::SkipFn => attrs.skip = Some(Skip::Fn(value)),
                         ValuefullName::SkipIf => attrs.skip = Some(Skip::If(value)),
                         ValuefullName::Wrapper => {
-                            attrs.bare_or_wrapper = Some(BareOrWrapper::Wrapper(value));
+                            attrs.repr = Some(Repr::Wrapper(value));
+                        }
+                        ValuefullName::MaxItems => {
+                            attrs.repr = Some(Repr::Items(Items {
+                                head: Some(value),
+                                tail: None,
+                            }));
+                        }
+                        ValuefullName::Head => attrs.items_mut().head = Some(value),
+                        ValuefullName::Level => attrs.level = Some(value),
+                        ValuefullName::Tail => attrs.items_mut().tail = Some(value),
+                        ValuefullName::Rename => {
+                            let AttrValue::LitStr(lit) = value else {
+                                panic!("`rename` must be a string literal");
+                            };
+                            attrs.rename = Some(lit);
+                        }
+                        ValuefullName::UnixTime => {
+                            let unit = TimeUnit::parse(&value)
+                                .expect("`unix_time` must be either `secs` or `millis`");
+                            attrs.repr = Some(Repr::UnixTime(unit));
+                        }
+                        ValuefullName::View => {
+                            let AttrValue::View(view) = value else {
+                                unreachable!()
+                            };
+                            attrs.views.push(view);
                         }
                     }
                 }
@@ -89,6 +253,16 @@ impl TryFrom<Vec<Attr>> for Attrs {
                     ValuelessName::Skip => attrs.skip = Some(Skip::Bare),
                     ValuelessName::SkipDefault => attrs.skip = Some(Skip::Default),
                     ValuelessName::NoSkip => attrs.skip = Some(Skip::No),
+                    ValuelessName::Hex => attrs.repr = Some(Repr::Hex),
+                    ValuelessName::Bytes => attrs.repr = Some(Repr::ByteStr),
+                    ValuelessName::BytesSize => attrs.repr = Some(Repr::ByteSize),
+                    ValuelessName::Duration => attrs.repr = Some(Repr::Duration),
+                    ValuelessName::Inline => attrs.inline = true,
+                    ValuelessName::PrettyOnly => attrs.only = Some(Only::Pretty),
+                    ValuelessName::CompactOnly => attrs.only = Some(Only::Compact),
+                    ValuelessName::FmtTrait(fmt_trait) => {
+                        attrs.repr = Some(Repr::FmtTrait(fmt_trait))
+                    }
                 },
             }
         }
@@ -118,7 +292,12 @@ impl Parse for Attr {
             None => todo!(),
         };
 
-        let value = if input.peek(Token![=]) {
+        let value = if let AttrName::Valuefull(ValuefullName::View) = name {
+            // `view(Name, ...)` holds other attrs instead of exprs
+            let nested;
+            parenthesized!(nested in input);
+            Some(AttrValue::View(View::parse_contents(&nested)?))
+        } else if input.peek(Token![=]) {
             // `name = value` attributes.
             // TODO: vv
             let _assign_token = input.parse::<Token![=]>()?; // skip '='
@@ -155,15 +334,32 @@ pub enum AttrName {
 
 #[derive(Clone, Debug)]
 pub enum ValuefullName {
+    Alt,
+    AltWrapper,
     Bare,
+    Head,
+    Level,
+    MaxItems,
+    Rename,
     SkipFn,
     SkipIf,
+    Tail,
+    UnixTime,
+    View,
     Wrapper,
 }
 
 #[derive(Clone, Debug)]
 pub enum ValuelessName {
+    Bytes,
+    BytesSize,
+    CompactOnly,
+    Duration,
+    FmtTrait(FmtTrait),
+    Hex,
+    Inline,
     NoSkip,
+    PrettyOnly,
     Skip,
     SkipDefault,
 }
@@ -171,11 +367,34 @@ pub enum ValuelessName {
 impl AttrName {
     fn new(ident: Ident) -> Option<Self> {
         let name = match ident.to_string().as_str() {
+            "alt" => Self::Valuefull(ValuefullName::Alt),
+            "alt_wrapper" => Self::Valuefull(ValuefullName::AltWrapper),
+            "binary" => Self::Valueless(ValuelessName::FmtTrait(FmtTrait::Binary)),
+            "bytes" => Self::Valueless(ValuelessName::Bytes),
+            "bytes_size" => Self::Valueless(ValuelessName::BytesSize),
+            "compact_only" => Self::Valueless(ValuelessName::CompactOnly),
+            "display" => Self::Valueless(ValuelessName::FmtTrait(FmtTrait::Display)),
+            "duration" => Self::Valueless(ValuelessName::Duration),
+            "head" => Self::Valuefull(ValuefullName::Head),
+            "hex" => Self::Valueless(ValuelessName::Hex),
+            "inline" => Self::Valueless(ValuelessName::Inline),
+            "level" => Self::Valuefull(ValuefullName::Level),
+            "lower_exp" => Self::Valueless(ValuelessName::FmtTrait(FmtTrait::LowerExp)),
+            "lower_hex" => Self::Valueless(ValuelessName::FmtTrait(FmtTrait::LowerHex)),
+            "max_items" => Self::Valuefull(ValuefullName::MaxItems),
             "no_skip" => Self::Valueless(ValuelessName::NoSkip),
+            "octal" => Self::Valueless(ValuelessName::FmtTrait(FmtTrait::Octal)),
+            "pretty_only" => Self::Valueless(ValuelessName::PrettyOnly),
+            "rename" => Self::Valuefull(ValuefullName::Rename),
             "skip_default" => Self::Valueless(ValuelessName::SkipDefault),
             "skip" => Self::Valueless(ValuelessName::Skip),
             "skip_fn" => Self::Valuefull(ValuefullName::SkipFn),
             "skip_if" => Self::Valuefull(ValuefullName::SkipIf),
+            "tail" => Self::Valuefull(ValuefullName::Tail),
+            "unix_time" => Self::Valuefull(ValuefullName::UnixTime),
+            "upper_exp" => Self::Valueless(ValuelessName::FmtTrait(FmtTrait::UpperExp)),
+            "upper_hex" => Self::Valueless(ValuelessName::FmtTrait(FmtTrait::UpperHex)),
+            "view" => Self::Valuefull(ValuefullName::View),
             "wrapper" => Self::Valuefull(ValuefullName::Wrapper),
             _ => return None,
         };
@@ -190,6 +409,7 @@ pub enum AttrValue {
     LitStr(LitStr),
     Expr(Expr),
     Call(Vec<Expr>),
+    View(View<Attr>),
 }
 
 impl ToTokens for AttrValue {
@@ -201,6 +421,7 @@ impl ToTokens for AttrValue {
                 let t = quote!(#(#t),*);
                 t.to_tokens(tokens)
             }
+            Self::View(_) => unreachable!("Views are never used as values"),
         }
     }
 }
@@ -1,2 +1,48 @@
 pub mod container;
 pub mod field;
+
+use std::iter::FromIterator;
+
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Path, Token,
+};
+
+/// The attrs within a `view(Name, ...)` attr where `A` is either a container or a field attr
+#[derive(Clone, Debug)]
+pub struct View<A> {
+    pub name: Path,
+    pub attrs: Vec<A>,
+}
+
+impl<A: Parse> View<A> {
+    pub fn parse_contents(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let attrs = if input.is_empty() {
+            Vec::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Vec::from_iter(Punctuated::<A, Token![,]>::parse_terminated(input)?)
+        };
+
+        Ok(Self { name, attrs })
+    }
+}
+
+impl<A: Clone> View<A> {
+    /// Every attr that's set within any of the `views` with the given `name`
+    pub fn overrides(views: &[Self], name: &Path) -> Vec<A> {
+        views
+            .iter()
+            .filter(|view| is_same_path(&view.name, name))
+            .flat_map(|view| view.attrs.iter().cloned())
+            .collect()
+    }
+}
+
+/// Views are named by paths, so two names are the same view when they're written the same way
+pub fn is_same_path(left: &Path, right: &Path) -> bool {
+    left.to_token_stream().to_string() == right.to_token_stream().to_string()
+}
@@ -1,78 +1,430 @@
 use crate::{
     attr::{container, field},
-    utils,
+    utils, view_impl,
 };
 
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse::Result, DeriveInput, Fields, FieldsNamed, FieldsUnnamed};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse::Result, DeriveInput, Field, Fields, FieldsUnnamed, Ident, LitStr, Member,
+    Path,
+};
+
+/// The `std::fmt` trait that gets implemented
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Debug,
+    Display,
+}
+
+impl Target {
+    /// The name of the attribute that's used for configuring this target e.g. `#[debug(...)]`
+    pub fn namespace(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Display => "display",
+        }
+    }
+
+    fn trait_path(self) -> TokenStream {
+        match self {
+            Self::Debug => quote! { ::core::fmt::Debug },
+            Self::Display => quote! { ::core::fmt::Display },
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Lets the snapshots cover the code generated with and without depth tracking regardless of
+    /// which features the tests are run with
+    pub static TRACK_DEPTH: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Whether `Debug` impls track their depth which is only done with the `depth` feature since it
+/// costs every value a couple of thread-local accesses
+fn tracks_depth() -> bool {
+    #[cfg(test)]
+    return TRACK_DEPTH.with(std::cell::Cell::get);
+    #[cfg(not(test))]
+    return cfg!(feature = "depth");
+}
+
+/// The path to the `smart_debug` crate that generated code uses
+pub fn crate_tt(crate_path: Option<Path>) -> TokenStream {
+    match crate_path {
+        Some(path) => quote! { #path },
+        None => quote! { ::smart_debug },
+    }
+}
+
+/// How the generated code refers to the field e.g. `self.#member`
+pub fn field_member(field_num: usize, field: &Field) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(syn::Index::from(field_num)),
+    }
+}
+
+/// The name that the field is displayed with. Tuple fields go by their index
+pub fn field_label(field_num: usize, field: &Field, rename: Option<LitStr>) -> String {
+    match (rename, &field.ident) {
+        (Some(rename), _) => rename.value(),
+        (None, Some(ident)) => ident.unraw().to_string(),
+        (None, None) => field_num.to_string(),
+    }
+}
+
+/// The fields that get displayed at all. A custom container repr has no fields to speak of
+pub fn shown_fields<'fields>(
+    fields: &'fields Fields,
+    container_bare: &Option<LitStr>,
+) -> impl Iterator<Item = (usize, &'fields Field)> {
+    let is_bare = container_bare.is_some();
+    fields.iter().filter(move |_| !is_bare).enumerate()
+}
 
-pub fn impl_derive(input: &DeriveInput) -> Result<TokenStream> {
+/// The default container that `skip_defaults` compares each field against
+pub fn container_defaults_tt(
+    name: &Ident,
+    container_skip: &Option<container::Skip>,
+) -> TokenStream {
+    match container_skip {
+        None | Some(container::Skip::Bare) => TokenStream::new(),
+        Some(container::Skip::Defaults) => {
+            quote! { let container_default = <#name>::default(); }
+        }
+    }
+}
+
+pub fn impl_derive(input: &DeriveInput, target: Target) -> Result<TokenStream> {
+    let name = &input.ident;
+    let trait_path = target.trait_path();
+    let generics = utils::with_bounds(input, target, &trait_path)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut fn_body = fmt_body_tt(input, target, None)?;
+    // Nested types follow along with whatever view is currently being formatted
+    if target == Target::Debug {
+        let view_dispatch = view_impl::dispatch_tt(input)?;
+        fn_body = quote! {
+            #view_dispatch
+            #fn_body
+        };
+    }
+
+    let fmt_impl = quote! {
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #fn_body
+            }
+        }
+    };
+
+    Ok(fmt_impl)
+}
+
+/// The body of the formatting function that follows the attrs for the named `view` if there is one
+pub fn fmt_body_tt(
+    input: &DeriveInput,
+    target: Target,
+    view: Option<&Path>,
+) -> Result<TokenStream> {
     let name = &input.ident;
-    let name_lit_str = name.to_string();
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let name_lit_str = name.unraw().to_string();
 
     let container::Attrs {
         skip: container_skip,
         bare: container_bare,
-    } = container::Attrs::parse(&input.attrs)?;
+        inline_fields,
+        marker: container_marker,
+        placeholder,
+        tuple_skip,
+        indexed,
+        crate_path,
+        cycle_safe,
+        max_depth,
+        views: container_views,
+    } = container::Attrs::parse(&input.attrs, target.namespace())?.in_view(view)?;
+    let krate = crate_tt(crate_path);
+
+    let fields = utils::struct_fields(input)?;
+
+    let tuple_style = TupleStyle {
+        indexed,
+        skip: tuple_skip.unwrap_or(container::TupleSkip::Placeholder),
+        placeholder: placeholder.map_or_else(|| "_".to_owned(), |lit| lit.value()),
+    };
+    // `#[non_exhaustive]` types get a marker since there could be more fields later on unless the
+    // marker is explicitly turned off with `exhaustive_marker = false`
+    let is_non_exhaustive = container_marker != Some(container::Marker::None)
+        && input
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("non_exhaustive"));
+
+    // Values nested past the depth limit are collapsed to just their name
+    let collapsed = match fields {
+        _ if !tracks_depth() || target != Target::Debug || container_bare.is_some() => None,
+        Fields::Named(_) => Some(quote! { f.debug_struct(#name_lit_str).finish_non_exhaustive() }),
+        Fields::Unnamed(_) if indexed => {
+            Some(quote! { f.debug_struct(#name_lit_str).finish_non_exhaustive() })
+        }
+        Fields::Unnamed(_) => {
+            let collapsed_lit_str = format!("{name_lit_str}(..)");
+            Some(quote! { f.write_str(#collapsed_lit_str) })
+        }
+        // Unit structs have nothing nested within them to collapse
+        Fields::Unit => None,
+    };
+    let depth_guard = match collapsed {
+        Some(collapsed) => {
+            let set_limit = max_depth.map(|max_depth| quote! { _depth.limit(#max_depth); });
+            quote! {
+                let _depth = match #krate::internal::__DepthGuard::enter() {
+                    ::core::option::Option::Some(depth) => depth,
+                    ::core::option::Option::None => return #collapsed,
+                };
+                #set_limit
+            }
+        }
+        None => TokenStream::new(),
+    };
+
+    // Types without any attrs get the exact same code that `#[derive(Debug)]` would generate aside
+    // from tracking the depth when that's enabled
+    let is_plain_container = target == Target::Debug
+        && view.is_none()
+        && container_bare.is_none()
+        && container_skip.is_none()
+        && !inline_fields
+        && !indexed
+        && !is_non_exhaustive
+        && !cycle_safe
+        && container_views.is_empty();
+    if is_plain_container && fields_are_plain(fields, target)? {
+        let plain_body = plain_body_tt(fields, &name_lit_str);
+        return Ok(quote! {
+            #depth_guard
+            #plain_body
+        });
+    }
+
+    // The formatter is borrowed by the debug builder, so check for `{:#?}` up front
+    let mut uses_alternate = false;
+    // Nothing needs to be tracked for the marker when no fields can ever be skipped
+    let mut can_skip = false;
+    for field in fields {
+        let field::Attrs {
+            skip, only, level, ..
+        } = field::Attrs::parse(&field.attrs, target.namespace())?.in_view(view)?;
+        uses_alternate |= only.is_some();
+        can_skip |= !matches!(Skip::new(&container_skip, skip), Skip::No)
+            || only.is_some()
+            || level.is_some();
+    }
+
+    let is_tuple = matches!(fields, Fields::Unnamed(_)) && !indexed;
+    let marker = container_marker.unwrap_or(container::Marker::Dots);
+    let tracking = match marker {
+        _ if !can_skip => Tracking::None,
+        // Tuple structs only need a marker when their skipped fields are left off
+        _ if is_tuple && tuple_style.skip != container::TupleSkip::Omit => Tracking::None,
+        container::Marker::None => Tracking::None,
+        container::Marker::Dots if is_non_exhaustive => Tracking::None,
+        container::Marker::Dots => Tracking::Any,
+        container::Marker::Count | container::Marker::Names => Tracking::Each,
+    };
+
+    let mut prelude = container_defaults_tt(name, &container_skip);
+    if uses_alternate {
+        prelude.extend(quote! { let alternate = f.alternate(); });
+    }
+    // Revisiting a value that's already being formatted further up would recurse forever
+    let cycle_guard = if cycle_safe {
+        quote! {
+            let _visit = match #krate::internal::__VisitGuard::enter(self) {
+                ::core::option::Option::Some(visit) => visit,
+                ::core::option::Option::None => {
+                    let cycle = #krate::internal::__Cycle::new(#name_lit_str, self);
+                    return ::core::fmt::Debug::fmt(&cycle, f);
+                }
+            };
+        }
+    } else {
+        TokenStream::new()
+    };
 
     let fn_body = match container_bare {
+        // Display templates get to interpolate fields while everything else is just a plain str
+        Some(lit_str) if target == Target::Display && utils::needs_formatting(&lit_str.value()) => {
+            let template_expr =
+                template_tt(fields, &lit_str, &container_skip, target, view, &krate)?;
+            quote! {
+                #prelude
+                #template_expr
+            }
+        }
         Some(lit_str) => quote! { f.write_str(#lit_str) },
         None => {
-            let (body_expr, struct_kind) = match &input.data {
-                syn::Data::Struct(body) => body_tt(&body.fields, &container_skip)?,
-                _ => todo!("Only structs are currently supported"),
-            };
+            let (body_expr, struct_kind) = body_tt(
+                fields,
+                &container_skip,
+                inline_fields,
+                tracking,
+                &tuple_style,
+                target,
+                view,
+                &krate,
+            )?;
 
-            let container_defaults = match container_skip {
-                None | Some(container::Skip::Bare) => TokenStream::new(),
-                Some(container::Skip::Defaults) => {
-                    quote! { let container_default = <#name>::default(); }
-                }
+            let (std_builder, custom_builder) = match struct_kind {
+                StructKind::NonTuple => (
+                    quote! { f.debug_struct(#name_lit_str) },
+                    quote! { #krate::internal::__DebugStruct::new(f, #name_lit_str) },
+                ),
+                StructKind::Tuple => (
+                    quote! { f.debug_tuple(#name_lit_str) },
+                    quote! { #krate::internal::__DebugTuple::new(f, #name_lit_str) },
+                ),
+            };
+            // `fmt::DebugTuple::finish_non_exhaustive()` is newer than the MSRV
+            let may_be_non_exhaustive = tracking != Tracking::None || is_non_exhaustive;
+            let builder = match struct_kind {
+                StructKind::Tuple if may_be_non_exhaustive => custom_builder,
+                StructKind::NonTuple if tracking == Tracking::Each => custom_builder,
+                _ => std_builder,
+            };
+            let exhaustive_finish = if is_non_exhaustive {
+                quote! { debug.finish_non_exhaustive() }
+            } else {
+                quote! { debug.finish() }
             };
 
-            let formatting_code = match struct_kind {
-                StructKind::NonTuple => {
+            let formatting_code = match tracking {
+                Tracking::None => quote! {
+                    let mut debug = #builder;
+                    #body_expr
+                    #exhaustive_finish
+                },
+                Tracking::Any => quote! {
+                    let mut debug = #builder;
+                    let mut field_was_skipped = false;
+                    #body_expr
+                    if field_was_skipped {
+                        debug.finish_non_exhaustive()
+                    } else {
+                        debug.finish()
+                    }
+                },
+                Tracking::Each => {
+                    let num_fields = fields.len();
+                    let marker = match marker {
+                        container::Marker::Names => {
+                            let names = field_names(fields, target, view)?;
+                            quote! {
+                                #krate::internal::__HiddenNames {
+                                    names: &[#( #names ),*],
+                                    skipped: &skipped_fields,
+                                }
+                            }
+                        }
+                        _ => quote! { #krate::internal::__HiddenCount(&skipped_fields) },
+                    };
                     quote! {
-                        let mut debug = f.debug_struct(#name_lit_str);
-                        let mut field_was_skipped = false;
+                        let mut debug = #builder;
+                        let mut skipped_fields = [false; #num_fields];
                         #body_expr
-                        if field_was_skipped {
-                            debug.finish_non_exhaustive()
+                        if skipped_fields.contains(&true) {
+                            debug.finish_with_marker(&#marker)
                         } else {
-                            debug.finish()
+                            #exhaustive_finish
                         }
                     }
                 }
-                StructKind::Tuple => {
-                    quote! {
-                        let mut debug = f.debug_tuple(#name_lit_str);
-                        #body_expr
-                        debug.finish()
-                    }
-                }
             };
 
             quote! {
-                #container_defaults
+                #prelude
                 #formatting_code
             }
         }
     };
 
-    let debug_impl = quote! {
-        impl #impl_generics ::std::fmt::Debug for #name #ty_generics #where_clause {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                #fn_body
-            }
+    Ok(quote! {
+        #cycle_guard
+        #depth_guard
+        #fn_body
+    })
+}
+
+/// Whether none of the fields have any attrs that change how they're displayed
+fn fields_are_plain(fields: &Fields, target: Target) -> Result<bool> {
+    for field in fields {
+        let field::Attrs {
+            repr,
+            alt_repr,
+            skip,
+            only,
+            level,
+            inline,
+            rename,
+            views,
+        } = field::Attrs::parse(&field.attrs, target.namespace())?;
+        let is_plain = repr.is_none()
+            && alt_repr.is_none()
+            && skip.is_none()
+            && only.is_none()
+            && level.is_none()
+            && !inline
+            && rename.is_none()
+            && views.is_empty();
+        if !is_plain {
+            return Ok(false);
         }
-    };
+    }
+
+    Ok(true)
+}
+
+/// The same formatting code that `#[derive(Debug)]` generates
+///
+/// std's derive goes through the unstable `debug_struct_field{N}_finish()` helpers, so this is the
+/// builder chain that those helpers are shorthand for. Just like std the last field gets an extra
+/// reference, so that an unsized last field can still be passed along as a `&dyn Debug`
+fn plain_body_tt(fields: &Fields, name_lit_str: &str) -> TokenStream {
+    let last_field = fields.len().saturating_sub(1);
+    let values = fields.iter().enumerate().map(|(field_num, field)| {
+        let member = field_member(field_num, field);
+        if field_num == last_field {
+            quote! { &&self.#member }
+        } else {
+            quote! { &self.#member }
+        }
+    });
 
-    Ok(debug_impl)
+    match fields {
+        Fields::Named(_) => {
+            let names = fields
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .map(|ident| ident.unraw().to_string());
+            quote! {
+                f.debug_struct(#name_lit_str)
+                    #( .field(#names, #values) )*
+                    .finish()
+            }
+        }
+        Fields::Unnamed(_) => quote! {
+            f.debug_tuple(#name_lit_str)
+                #( .field(#values) )*
+                .finish()
+        },
+        Fields::Unit => quote! { f.write_str(#name_lit_str) },
+    }
 }
 
-enum Skip {
+pub enum Skip {
     No,
     Unconditional,
     Default,
@@ -83,7 +435,7 @@ enum Skip {
 
 impl Skip {
     // local takes precedence over global
-    fn new(global: &Option<container::Skip>, local: Option<field::Skip>) -> Self {
+    pub fn new(global: &Option<container::Skip>, local: Option<field::Skip>) -> Self {
         match (global, local) {
             (_, Some(field::Skip::No)) | (None, None) => Self::No,
             (Some(container::Skip::Bare), None) | (_, Some(field::Skip::Bare)) => {
@@ -95,6 +447,92 @@ impl Skip {
             (_, Some(field::Skip::Fn(value))) => Self::Fn(value),
         }
     }
+
+    /// Unconditionally skipped fields never get touched by the generated code, so they don't have
+    /// to implement whatever trait is being derived
+    pub fn is_unconditional(&self) -> bool {
+        matches!(self, Self::Unconditional)
+    }
+
+    /// The condition for skipping the field if it's ever skipped
+    pub fn cond_tt(self, member: &Member, ty: &syn::Type) -> Option<TokenStream> {
+        let cond = match self {
+            Self::No => return None,
+            Self::Unconditional => quote! { true },
+            Self::Default => quote! { self.#member == <#ty>::default() },
+            Self::DefaultGlobal => quote! { self.#member == container_default.#member },
+            Self::If(value) => quote! { self.#member == #value },
+            Self::Fn(value) => quote! { #value(&self.#member) },
+        };
+
+        Some(cond)
+    }
+}
+
+/// Adds the conditions for hiding `pretty_only`, `compact_only`, and `level` fields to the skip
+/// condition
+///
+/// `alternate` is whether the formatter is in alternate mode. Everything that doesn't have an
+/// alternate mode uses `false` which only displays the compact fields
+pub fn hidden_cond_tt(
+    cond: Option<TokenStream>,
+    only: Option<field::Only>,
+    level: Option<field::AttrValue>,
+    alternate: &TokenStream,
+    krate: &TokenStream,
+) -> Option<TokenStream> {
+    let only_cond = only.map(|only| match only {
+        field::Only::Pretty => quote! { !#alternate },
+        field::Only::Compact => quote! { #alternate },
+    });
+    let level_cond = level.map(|level| quote! { #krate::internal::__verbosity() < (#level) });
+
+    cond.into_iter()
+        .chain(only_cond)
+        .chain(level_cond)
+        .reduce(|cond, other| quote! { (#cond) || #other })
+}
+
+/// How skipped fields get tracked for the marker that's displayed in their place
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tracking {
+    /// The marker doesn't depend on what was skipped
+    None,
+    /// Only whether any field was skipped
+    Any,
+    /// Which fields specifically were skipped
+    Each,
+}
+
+impl Tracking {
+    fn record_tt(self, field_num: usize) -> TokenStream {
+        match self {
+            Self::None => TokenStream::new(),
+            Self::Any => quote! { field_was_skipped = true; },
+            Self::Each => quote! { skipped_fields[#field_num] = true; },
+        }
+    }
+}
+
+/// How a tuple struct gets displayed
+struct TupleStyle {
+    /// Displayed like a struct with each field's index as its name
+    indexed: bool,
+    skip: container::TupleSkip,
+    placeholder: String,
+}
+
+/// The labels that fields get displayed with. Tuple struct fields are labeled by their index
+fn field_names(fields: &Fields, target: Target, view: Option<&Path>) -> Result<Vec<String>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(field_num, field)| {
+            let field::Attrs { rename, .. } =
+                field::Attrs::parse(&field.attrs, target.namespace())?.in_view(view)?;
+            Ok(field_label(field_num, field, rename))
+        })
+        .collect()
 }
 
 enum StructKind {
@@ -104,136 +542,304 @@ enum StructKind {
 
 // TODO: the generated code here could be better by avoiding some code in different situations
 // TODO: the regular struct and tuple struct arms could share more code
+#[allow(clippy::too_many_arguments)]
 fn body_tt(
     fields: &Fields,
     global_skip: &Option<container::Skip>,
+    inline_fields: bool,
+    tracking: Tracking,
+    tuple_style: &TupleStyle,
+    target: Target,
+    view: Option<&Path>,
+    krate: &TokenStream,
 ) -> Result<(TokenStream, StructKind)> {
     match fields {
-        Fields::Named(FieldsNamed { named, .. }) => {
-            let formatted_fields = named
+        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) if !tuple_style.indexed => {
+            let placeholder = &tuple_style.placeholder;
+            let placeholder = quote! {
+                debug.field(&#krate::internal::__SkippedTupleField(#placeholder));
+            };
+            let mut conds = Vec::new();
+            let formatted_fields = unnamed
                 .iter()
-                .map(|field| {
-                    let field_name = &field.ident;
+                .enumerate()
+                .map(|(field_num, field)| {
+                    let member = Member::Unnamed(syn::Index::from(field_num));
                     let field::Attrs {
-                        bare_or_wrapper,
+                        repr,
+                        alt_repr,
                         skip: field_skip,
-                    } = field::Attrs::parse(&field.attrs)?;
+                        only,
+                        level,
+                        inline,
+                        rename: _,
+                        views: _,
+                    } = field::Attrs::parse(&field.attrs, target.namespace())?.in_view(view)?;
                     let skip = Skip::new(global_skip, field_skip);
-                    let maybe_cond = match skip {
-                        Skip::No => None,
-                        Skip::Unconditional => {
-                            return Ok(quote! { field_was_skipped = true; });
-                        }
-                        Skip::Default => {
-                            let ty = field.ty.to_owned();
-                            Some(quote! { self.#field_name == <#ty>::default() })
-                        }
-                        Skip::DefaultGlobal => {
-                            Some(quote! { self.#field_name == container_default.#field_name })
-                        }
-                        Skip::If(value) => Some(quote! { self.#field_name == #value }),
-                        Skip::Fn(value) => Some(quote! { #value(&self.#field_name) }),
-                    };
+                    let cond = hidden_cond_tt(
+                        skip.cond_tt(&member, &field.ty),
+                        only,
+                        level,
+                        &quote! { alternate },
+                        krate,
+                    )
+                    .unwrap_or_else(|| quote! { false });
 
-                    let field_tokens = match bare_or_wrapper {
-                        Some(field::BareOrWrapper::Bare(bare)) => {
-                            let has_interpol = utils::needs_formatting(&bare.value());
+                    let field_tokens = value_tt(
+                        repr,
+                        alt_repr,
+                        inline || inline_fields,
+                        &member,
+                        target,
+                        krate,
+                    );
 
-                            // Use `format_args!()` if it's an interpolated str
-                            let args = if has_interpol {
-                                quote! { #bare, &self.#field_name }
+                    let field_tokens = match tuple_style.skip {
+                        container::TupleSkip::Placeholder => quote! {
+                            if #cond {
+                                #placeholder
                             } else {
-                                quote! { #bare }
-                            };
+                                debug.field(&#field_tokens);
+                            }
+                        },
+                        container::TupleSkip::Omit => {
+                            let record_skip = tracking.record_tt(field_num);
                             quote! {
-                                ::smart_debug::internal::__LiteralField(::std::format_args!(#args))
+                                if #cond {
+                                    #record_skip
+                                } else {
+                                    debug.field(&#field_tokens);
+                                }
                             }
                         }
-                        Some(field::BareOrWrapper::Wrapper(wrapper)) => {
-                            quote! { #wrapper(&self.#field_name) }
-                        }
-                        None => quote! { self.#field_name },
-                    };
-
-                    let field_name_str = field_name.as_ref().unwrap().to_string();
-                    let field_tokens = match maybe_cond {
-                        Some(cond_value) => {
+                        container::TupleSkip::Trailing => {
+                            conds.push(cond);
                             quote! {
-                                if #cond_value {
-                                    field_was_skipped = true;
-                                } else {
-                                    debug.field(#field_name_str, &#field_tokens);
+                                if #field_num < num_shown {
+                                    if tuple_skipped[#field_num] {
+                                        #placeholder
+                                    } else {
+                                        debug.field(&#field_tokens);
+                                    }
                                 }
                             }
                         }
-                        None => quote! {
-                            debug.field(#field_name_str, &#field_tokens);
-                        },
                     };
                     Ok(field_tokens)
                 })
                 .collect::<Result<Vec<_>>>()?;
-            let tokens = quote! { #( #formatted_fields )* };
-            Ok((tokens, StructKind::NonTuple))
+            let tokens = match tuple_style.skip {
+                // Skipped fields are only displayed when there's a displayed field after them
+                container::TupleSkip::Trailing => quote! {
+                    let tuple_skipped = [#( #conds ),*];
+                    let num_shown = tuple_skipped
+                        .iter()
+                        .rposition(|skipped| !skipped)
+                        .map_or(0, |last| last + 1);
+                    #( #formatted_fields )*
+                },
+                _ => quote! { #( #formatted_fields )* },
+            };
+            Ok((tokens, StructKind::Tuple))
         }
-        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-            let formatted_fields = unnamed
+        // Indexed tuple structs are displayed just like regular structs
+        Fields::Named(_) | Fields::Unnamed(_) => {
+            let formatted_fields = fields
                 .iter()
                 .enumerate()
                 .map(|(field_num, field)| {
-                    let field_name = syn::Index::from(field_num);
+                    let member = field_member(field_num, field);
                     let field::Attrs {
-                        bare_or_wrapper,
+                        repr,
+                        alt_repr,
                         skip: field_skip,
-                    } = field::Attrs::parse(&field.attrs)?;
+                        only,
+                        level,
+                        inline,
+                        rename,
+                        views: _,
+                    } = field::Attrs::parse(&field.attrs, target.namespace())?.in_view(view)?;
                     let skip = Skip::new(global_skip, field_skip);
-                    let cond = match skip {
-                        Skip::No => quote! { false },
-                        Skip::Unconditional => quote! { true },
-                        Skip::Default => {
-                            let ty = field.ty.to_owned();
-                            quote! { self.#field_name == <#ty>::default() }
-                        }
-                        Skip::DefaultGlobal => {
-                            quote! { self.#field_name == container_default.#field_name }
-                        }
-                        Skip::If(value) => quote! { self.#field_name == #value },
-                        Skip::Fn(value) => quote! { #value(&self.#field_name) },
-                    };
+                    let record_skip = tracking.record_tt(field_num);
+                    if skip.is_unconditional() {
+                        return Ok(record_skip);
+                    }
+                    let maybe_cond = hidden_cond_tt(
+                        skip.cond_tt(&member, &field.ty),
+                        only,
+                        level,
+                        &quote! { alternate },
+                        krate,
+                    );
 
-                    let field_tokens = match bare_or_wrapper {
-                        Some(field::BareOrWrapper::Bare(bare)) => {
-                            let has_interpol = utils::needs_formatting(&bare.value());
+                    let field_tokens = value_tt(
+                        repr,
+                        alt_repr,
+                        inline || inline_fields,
+                        &member,
+                        target,
+                        krate,
+                    );
 
-                            // Use `format_args!()` if it's an interpolated str
-                            let args = if has_interpol {
-                                quote! { #bare, &self.#field_name }
-                            } else {
-                                quote! { #bare }
-                            };
+                    let field_name_str = field_label(field_num, field, rename);
+                    let field_tokens = match maybe_cond {
+                        Some(cond_value) => {
                             quote! {
-                                ::smart_debug::internal::__LiteralField(::std::format_args!(#args))
+                                if #cond_value {
+                                    #record_skip
+                                } else {
+                                    debug.field(#field_name_str, &#field_tokens);
+                                }
                             }
                         }
-                        Some(field::BareOrWrapper::Wrapper(wrapper)) => {
-                            quote! { #wrapper(&self.#field_name) }
-                        }
-                        None => quote! { self.#field_name },
-                    };
-
-                    let field_tokens = quote! {
-                        if #cond {
-                            debug.field(&::smart_debug::internal::__SkippedTupleField);
-                        } else {
-                            debug.field(&#field_tokens);
-                        }
+                        None => quote! {
+                            debug.field(#field_name_str, &#field_tokens);
+                        },
                     };
                     Ok(field_tokens)
                 })
                 .collect::<Result<Vec<_>>>()?;
             let tokens = quote! { #( #formatted_fields )* };
-            Ok((tokens, StructKind::Tuple))
+            Ok((tokens, StructKind::NonTuple))
         }
         Fields::Unit => Ok((TokenStream::new(), StructKind::NonTuple)),
     }
 }
+
+/// A `Display` impl's container template with fields interpolated into it
+fn template_tt(
+    fields: &Fields,
+    template: &LitStr,
+    global_skip: &Option<container::Skip>,
+    target: Target,
+    view: Option<&Path>,
+    krate: &TokenStream,
+) -> Result<TokenStream> {
+    // Positional args get renamed so that they can be passed as named args e.g. `{0}` -> `{_0}`
+    let (template_str, template_args) = utils::template_args(&template.value());
+    let template = LitStr::new(&template_str, template.span());
+
+    let mut args = Vec::new();
+    for (field_num, field) in fields.iter().enumerate() {
+        let member = field_member(field_num, field);
+        let arg_name = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("_{}", field_num),
+        };
+        // Only referenced fields can be passed in. Otherwise we get unused argument errors
+        if !template_args.iter().any(|arg| arg_name == arg.name) {
+            continue;
+        }
+
+        let field::Attrs {
+            repr,
+            alt_repr,
+            skip: field_skip,
+            only,
+            level,
+            inline,
+            rename: _,
+            views: _,
+        } = field::Attrs::parse(&field.attrs, target.namespace())?.in_view(view)?;
+        let skip = Skip::new(global_skip, field_skip);
+        let skipped = hidden_cond_tt(
+            skip.cond_tt(&member, &field.ty),
+            only,
+            level,
+            &quote! { alternate },
+            krate,
+        )
+        .unwrap_or_else(|| quote! { false });
+        let value = match (repr, alt_repr) {
+            (None, None) => quote! { &self.#member },
+            (repr, alt_repr) => {
+                let value = value_tt(repr, alt_repr, inline, &member, target, krate);
+                quote! { &#krate::internal::__DebugAsDisplay(&#value) }
+            }
+        };
+
+        args.push(quote! {
+            #arg_name = #krate::internal::__DisplayField::new(#skipped, #value)
+        });
+    }
+
+    Ok(quote! { f.write_fmt(::core::format_args!(#template, #( #args ),*)) })
+}
+
+/// The expression for the value that gets passed on to the debug builder
+///
+/// `alt_repr` takes over from `repr` when the formatter is in alternate mode
+pub fn value_tt(
+    repr: Option<field::Repr>,
+    alt_repr: Option<field::Repr>,
+    inline: bool,
+    member: &Member,
+    target: Target,
+    krate: &TokenStream,
+) -> TokenStream {
+    let field = quote! { self.#member };
+    let value = match repr {
+        Some(field::Repr::Bare(bare)) => {
+            let has_interpol = utils::needs_formatting(&bare.value());
+
+            // Use `format_args!()` if it's an interpolated str
+            let args = if has_interpol {
+                quote! { #bare, &#field }
+            } else {
+                quote! { #bare }
+            };
+            quote! {
+                #krate::internal::__LiteralField(::core::format_args!(#args))
+            }
+        }
+        Some(field::Repr::Wrapper(wrapper)) => quote! { #wrapper(&#field) },
+        Some(field::Repr::Items(field::Items { head, tail })) => {
+            let head = head.map_or_else(|| quote! { 0 }, |head| quote! { #head });
+            let tail = tail.map_or_else(|| quote! { 0 }, |tail| quote! { #tail });
+            quote! { #krate::Elided::new(&#field, #head, #tail) }
+        }
+        Some(field::Repr::Hex) => quote! { #krate::Hex(&#field) },
+        Some(field::Repr::ByteStr) => quote! { #krate::ByteStr(&#field) },
+        Some(field::Repr::ByteSize) => quote! { #krate::ByteSize(&#field) },
+        Some(field::Repr::Duration) => quote! { #krate::HumanDuration(&#field) },
+        Some(field::Repr::UnixTime(unit)) => {
+            let unit = match unit {
+                field::TimeUnit::Secs => quote! { #krate::TimeUnit::Secs },
+                field::TimeUnit::Millis => quote! { #krate::TimeUnit::Millis },
+            };
+            quote! { #krate::UnixTime::new(&#field, #unit) }
+        }
+        Some(field::Repr::FmtTrait(fmt_trait)) => {
+            let wrapper = match fmt_trait {
+                field::FmtTrait::Display => quote! { AsDisplay },
+                field::FmtTrait::LowerHex => quote! { AsLowerHex },
+                field::FmtTrait::UpperHex => quote! { AsUpperHex },
+                field::FmtTrait::Binary => quote! { AsBinary },
+                field::FmtTrait::Octal => quote! { AsOctal },
+                field::FmtTrait::LowerExp => quote! { AsLowerExp },
+                field::FmtTrait::UpperExp => quote! { AsUpperExp },
+            };
+            quote! { #krate::#wrapper(&#field) }
+        }
+        None => match target {
+            Target::Debug => field,
+            Target::Display => quote! { #krate::AsDisplay(&#field) },
+        },
+    };
+
+    let value = if inline {
+        quote! { #krate::DebugInline(&#value) }
+    } else {
+        value
+    };
+
+    // Each repr gets inlined on its own since `DebugInline` drops the `{:#?}` flag that picks the
+    // alt repr
+    match alt_repr {
+        Some(alt_repr) => {
+            let alt_value = value_tt(Some(alt_repr), None, inline, member, target, krate);
+            quote! { #krate::internal::__AltField(&#value, &#alt_value) }
+        }
+        None => value,
+    }
+}
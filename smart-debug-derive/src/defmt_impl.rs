@@ -0,0 +1,175 @@
+use crate::{
+    attr::{container, field},
+    body_impl::{self, Skip, Target},
+    utils,
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, parse::Result, DeriveInput, Fields, LitStr};
+
+/// Generates a `defmt::Format` impl that follows the same `#[debug(...)]` attrs as `SmartDebug`
+///
+/// The impl gets written out piece by piece since which fields are displayed is only known at
+/// runtime. Field values go through `defmt`'s own formatting when they're displayed as is, and
+/// fall back to `defmt::Debug2Format` for anything with a custom representation
+pub fn impl_derive(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let generics = utils::with_bounds(input, Target::Debug, &quote! { ::defmt::Format })?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let container = container::Attrs::parse(&input.attrs, Target::Debug.namespace())?;
+    let krate = body_impl::crate_tt(container.crate_path);
+
+    let fields = utils::struct_fields(input)?;
+
+    let fn_body = match container.bare {
+        Some(lit_str) => {
+            let lit_str = LitStr::new(&escape_braces(&lit_str.value()), lit_str.span());
+            quote! { ::defmt::write!(f, #lit_str); }
+        }
+        None => {
+            let container_defaults = body_impl::container_defaults_tt(name, &container.skip);
+            let name_lit_str = LitStr::new(&name.unraw().to_string(), name.span());
+            let body_expr = body_tt(fields, &container.skip, &krate)?;
+
+            quote! {
+                #container_defaults
+                ::defmt::write!(f, #name_lit_str);
+                #body_expr
+            }
+        }
+    };
+
+    let defmt_impl = quote! {
+        impl #impl_generics ::defmt::Format for #name #ty_generics #where_clause {
+            fn format(&self, f: ::defmt::Formatter<'_>) {
+                #fn_body
+            }
+        }
+    };
+
+    Ok(defmt_impl)
+}
+
+fn body_tt(
+    fields: &Fields,
+    global_skip: &Option<container::Skip>,
+    krate: &TokenStream,
+) -> Result<TokenStream> {
+    if fields.is_empty() {
+        return Ok(TokenStream::new());
+    }
+
+    let formatted_fields = fields
+        .iter()
+        .enumerate()
+        .map(|(field_num, field)| {
+            let member = body_impl::field_member(field_num, field);
+            let field::Attrs {
+                repr,
+                alt_repr: _,
+                skip: field_skip,
+                only,
+                level,
+                inline: _,
+                rename,
+                views: _,
+            } = field::Attrs::parse(&field.attrs, Target::Debug.namespace())?;
+            let skip = Skip::new(global_skip, field_skip);
+            let is_unconditional = skip.is_unconditional();
+            let cond = body_impl::hidden_cond_tt(
+                skip.cond_tt(&member, &field.ty),
+                only,
+                level,
+                &quote! { false },
+                krate,
+            )
+            .unwrap_or_else(|| quote! { false });
+
+            // `{=str}` is used for the separator since it's only known at runtime
+            let (label, skipped) = match &field.ident {
+                Some(_) => (
+                    format!(
+                        "{{=str}}{}: ",
+                        body_impl::field_label(field_num, field, rename)
+                    ),
+                    quote! { field_was_skipped = true; },
+                ),
+                None => (
+                    "{=str}".to_owned(),
+                    quote! {
+                        ::defmt::write!(f, "{=str}_", sep);
+                        is_first = false;
+                    },
+                ),
+            };
+            let write_field = match repr {
+                // Non-interpolated strs can be interned directly
+                Some(field::Repr::Bare(bare)) if !utils::needs_formatting(&bare.value()) => {
+                    let fmt_str = LitStr::new(&format!("{label}{}", bare.value()), bare.span());
+                    quote! { ::defmt::write!(f, #fmt_str, sep); }
+                }
+                None => {
+                    let fmt_str =
+                        LitStr::new(&format!("{label}{{}}"), proc_macro2::Span::call_site());
+                    quote! { ::defmt::write!(f, #fmt_str, sep, self.#member); }
+                }
+                Some(repr) => {
+                    let fmt_str =
+                        LitStr::new(&format!("{label}{{}}"), proc_macro2::Span::call_site());
+                    let value =
+                        body_impl::value_tt(Some(repr), None, false, &member, Target::Debug, krate);
+                    quote! { ::defmt::write!(f, #fmt_str, sep, ::defmt::Debug2Format(&#value)); }
+                }
+            };
+
+            let sep = quote! { let sep = if is_first { "" } else { ", " }; };
+            if is_unconditional {
+                return Ok(match field.ident {
+                    Some(_) => skipped,
+                    None => quote! {
+                        #sep
+                        #skipped
+                    },
+                });
+            }
+
+            Ok(quote! {
+                #sep
+                if #cond {
+                    #skipped
+                } else {
+                    #write_field
+                    is_first = false;
+                }
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let tokens = match fields {
+        Fields::Named(_) => quote! {
+            ::defmt::write!(f, " {{ ");
+            let mut is_first = true;
+            let mut field_was_skipped = false;
+            #( #formatted_fields )*
+            if field_was_skipped {
+                ::defmt::write!(f, "{=str}..", if is_first { "" } else { ", " });
+            }
+            ::defmt::write!(f, " }}");
+        },
+        Fields::Unnamed(_) => quote! {
+            ::defmt::write!(f, "(");
+            let mut is_first = true;
+            #( #formatted_fields )*
+            ::defmt::write!(f, ")");
+        },
+        Fields::Unit => unreachable!("Unit structs have no fields"),
+    };
+
+    Ok(tokens)
+}
+
+fn escape_braces(s: &str) -> String {
+    s.replace('{', "{{").replace('}', "}}")
+}
@@ -0,0 +1,121 @@
+use crate::{
+    attr::{container, field},
+    body_impl::{self, Skip, Target},
+    utils,
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Result, DeriveInput};
+
+/// Generates a `DebugFields` impl that visits the same fields, formatted the same way, as the
+/// `Debug` impl
+pub fn impl_derive(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let generics = utils::with_bounds(input, Target::Debug, &quote! { ::core::fmt::Debug })?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let container = container::Attrs::parse(&input.attrs, Target::Debug.namespace())?;
+    let krate = body_impl::crate_tt(container.crate_path);
+    let inline_fields = container.inline_fields;
+
+    let fields = utils::struct_fields(input)?;
+    let container_defaults = body_impl::container_defaults_tt(name, &container.skip);
+
+    let last_field = fields.len().saturating_sub(1);
+    // Nothing needs to be counted when no fields can ever be skipped
+    let mut can_skip = false;
+    let visited_fields = body_impl::shown_fields(fields, &container.bare)
+        .map(|(field_num, field)| {
+            let member = body_impl::field_member(field_num, field);
+            let field::Attrs {
+                repr,
+                alt_repr,
+                skip: field_skip,
+                only,
+                level,
+                inline,
+                rename,
+                views: _,
+            } = field::Attrs::parse(&field.attrs, Target::Debug.namespace())?;
+            let skip = Skip::new(&container.skip, field_skip);
+            if skip.is_unconditional() {
+                can_skip = true;
+                return Ok(quote! { skipped += 1; });
+            }
+            let maybe_cond = body_impl::hidden_cond_tt(
+                skip.cond_tt(&member, &field.ty),
+                only,
+                level,
+                &quote! { false },
+                &krate,
+            );
+            let label = body_impl::field_label(field_num, field, rename);
+            // Only fields that are displayed as is can be visited as nested fields
+            let is_nested = repr.is_none();
+            let is_wrapped = alt_repr.is_some() || inline || inline_fields;
+            let value = body_impl::value_tt(
+                repr,
+                alt_repr,
+                inline || inline_fields,
+                &member,
+                Target::Debug,
+                &krate,
+            );
+            let visit = if is_nested {
+                // Just like std's derive an unsized last field needs an extra reference to be
+                // passed along as a `&dyn Debug`
+                let value = if field_num == last_field && !is_wrapped {
+                    quote! { &#value }
+                } else {
+                    value
+                };
+                let nested = quote! { #krate::internal::__Nested(&self.#member) };
+                quote! {
+                    match (&#nested).__nested_fields() {
+                        Some(fields) => visitor.visit_nested(#label, &#value, fields),
+                        None => visitor.visit_field(#label, &#value),
+                    }
+                }
+            } else {
+                quote! { visitor.visit_field(#label, &#value); }
+            };
+
+            match maybe_cond {
+                Some(cond) => {
+                    can_skip = true;
+                    Ok(quote! {
+                        if #cond {
+                            skipped += 1;
+                        } else {
+                            #visit
+                        }
+                    })
+                }
+                None => Ok(visit),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (skipped_decl, skipped) = if can_skip {
+        (quote! { let mut skipped = 0; }, quote! { skipped })
+    } else {
+        (TokenStream::new(), quote! { 0 })
+    };
+
+    let fields_impl = quote! {
+        impl #impl_generics #krate::DebugFields for #name #ty_generics #where_clause {
+            fn visit(&self, visitor: &mut impl #krate::FieldVisitor) {
+                #[allow(unused_imports)]
+                use #krate::internal::{__LeafKind as _, __NestedKind as _};
+
+                #container_defaults
+                #skipped_decl
+                #( #visited_fields )*
+                visitor.visit_skipped(#skipped);
+            }
+        }
+    };
+
+    Ok(fields_impl)
+}
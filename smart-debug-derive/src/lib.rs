@@ -4,14 +4,79 @@ use syn::{parse_macro_input, DeriveInput};
 
 mod attr;
 mod body_impl;
+mod defmt_impl;
+mod fields_impl;
+mod log_kv_impl;
+#[cfg(test)]
+mod tests;
 mod utils;
+mod valuable_impl;
+mod view_impl;
+
+use body_impl::Target;
 
 /// Derive macro for deriving [`Debug`] with easier customization
 #[proc_macro_derive(SmartDebug, attributes(debug))]
 pub fn derive_smart_debug(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive(input, Target::Debug)
+}
+
+/// Derive macro for deriving [`Display`](std::fmt::Display) with the same customization as
+/// `SmartDebug`
+#[proc_macro_derive(SmartDisplay, attributes(display))]
+pub fn derive_smart_display(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive(input, Target::Display)
+}
+
+/// Derive macro for deriving `valuable::Valuable` that only visits the fields that `SmartDebug`
+/// would display
+#[proc_macro_derive(SmartValuable, attributes(debug))]
+pub fn derive_smart_valuable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match valuable_impl::impl_derive(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive macro for deriving `log::kv::Source` where each field that `SmartDebug` would display is
+/// its own key-value pair
+#[proc_macro_derive(SmartLogKv, attributes(debug))]
+pub fn derive_smart_log_kv(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match log_kv_impl::impl_derive(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive macro for deriving `defmt::Format` that follows the same `#[debug(...)]` attributes as
+/// `SmartDebug`
+#[proc_macro_derive(SmartDefmt, attributes(debug))]
+pub fn derive_smart_defmt(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match defmt_impl::impl_derive(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn derive(input: proc_macro::TokenStream, target: Target) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    match body_impl::impl_derive(&input) {
+    match expand(&input, target) {
         Ok(output) => output.into(),
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+fn expand(input: &DeriveInput, target: Target) -> syn::Result<proc_macro2::TokenStream> {
+    let mut output = body_impl::impl_derive(input, target)?;
+
+    // Everything else that follows the `Debug` impl's rules
+    if target == Target::Debug {
+        output.extend(fields_impl::impl_derive(input)?);
+        output.extend(view_impl::impl_derive(input)?);
+    }
+
+    Ok(output)
+}
@@ -0,0 +1,104 @@
+use crate::{
+    attr::{container, field},
+    body_impl::{self, Skip, Target},
+    utils,
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Result, DeriveInput};
+
+/// Generates a `log::kv::Source` impl where each field that would be displayed by the `Debug` impl
+/// is its own key-value pair
+///
+/// Values have to be borrowed for as long as the container, so fields with a custom
+/// representation go through `__FieldRef` which formats the field from a reference to the
+/// container itself
+pub fn impl_derive(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let container = container::Attrs::parse(&input.attrs, Target::Debug.namespace())?;
+    let krate = body_impl::crate_tt(container.crate_path);
+    let internal = quote! { #krate::internal };
+    let kv = quote! { #internal::log::kv };
+    let generics = utils::with_bounds(input, Target::Debug, &quote! { ::core::fmt::Debug })?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = utils::struct_fields(input)?;
+
+    let container_defaults = body_impl::container_defaults_tt(name, &container.skip);
+
+    let mut field_impls = Vec::new();
+    let mut visited_pairs = Vec::new();
+    for (field_num, field) in body_impl::shown_fields(fields, &container.bare) {
+        let member = body_impl::field_member(field_num, field);
+        let field::Attrs {
+            repr,
+            alt_repr: _,
+            skip: field_skip,
+            only,
+            level,
+            inline: _,
+            rename,
+            views: _,
+        } = field::Attrs::parse(&field.attrs, Target::Debug.namespace())?;
+        let skip = Skip::new(&container.skip, field_skip);
+        if skip.is_unconditional() {
+            continue;
+        }
+        let cond = body_impl::hidden_cond_tt(
+            skip.cond_tt(&member, &field.ty),
+            only,
+            level,
+            &quote! { false },
+            &krate,
+        )
+        .unwrap_or_else(|| quote! { false });
+
+        let value = match repr {
+            None => quote! { #kv::Value::from_debug(&self.#member) },
+            Some(repr) => {
+                let value =
+                    body_impl::value_tt(Some(repr), None, false, &member, Target::Debug, &krate);
+                field_impls.push(quote! {
+                    impl #impl_generics #internal::__FmtField<#field_num> for #name #ty_generics
+                        #where_clause
+                    {
+                        fn fmt_field(
+                            &self,
+                            f: &mut ::core::fmt::Formatter<'_>,
+                        ) -> ::core::fmt::Result {
+                            ::core::fmt::Debug::fmt(&#value, f)
+                        }
+                    }
+                });
+                quote! {
+                    #kv::Value::from_debug(#internal::__FieldRef::<Self, #field_num>::new(self))
+                }
+            }
+        };
+
+        let key = body_impl::field_label(field_num, field, rename);
+        visited_pairs.push(quote! {
+            if !(#cond) {
+                visitor.visit_pair(#kv::Key::from_str(#key), #value)?;
+            }
+        });
+    }
+
+    let log_kv_impl = quote! {
+        #( #field_impls )*
+
+        impl #impl_generics #kv::Source for #name #ty_generics #where_clause {
+            fn visit<'kvs>(
+                &'kvs self,
+                visitor: &mut dyn #kv::VisitSource<'kvs>,
+            ) -> ::core::result::Result<(), #kv::Error> {
+                #container_defaults
+                #( #visited_pairs )*
+                ::core::result::Result::Ok(())
+            }
+        }
+    };
+
+    Ok(log_kv_impl)
+}
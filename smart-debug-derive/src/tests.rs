@@ -0,0 +1,283 @@
+//! Snapshots of the generated code, so that any changes to it get reviewed explicitly
+
+use crate::{body_impl::Target, defmt_impl, fields_impl, view_impl};
+
+use proc_macro2::TokenStream;
+use syn::{parse_quote, DeriveInput};
+
+fn pretty(tokens: TokenStream) -> String {
+    let file = syn::parse2(tokens).expect("Generated code should parse");
+    prettyplease::unparse(&file)
+}
+
+/// Everything `SmartDebug` generates regardless of which features are enabled
+fn expand_debug(input: DeriveInput) -> String {
+    let mut output = crate::body_impl::impl_derive(&input, Target::Debug).unwrap();
+    output.extend(fields_impl::impl_derive(&input).unwrap());
+    output.extend(view_impl::impl_derive(&input).unwrap());
+    pretty(output)
+}
+
+fn expand_display(input: DeriveInput) -> String {
+    pretty(crate::body_impl::impl_derive(&input, Target::Display).unwrap())
+}
+
+#[test]
+fn plain() {
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        struct Named<'a, T> {
+            id: u32,
+            name: &'a str,
+            items: Vec<T>,
+        }
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        struct Tuple(u8, (), [u8]);
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        struct Unit;
+    }));
+}
+
+#[test]
+fn named_field_attrs() {
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(skip_defaults, inline_fields)]
+        struct Named {
+            #[debug(skip)]
+            skipped: u8,
+            #[debug(no_skip)]
+            never_skipped: u8,
+            #[debug(skip_default)]
+            skip_default: u8,
+            #[debug(skip_if = 0)]
+            skip_if: u8,
+            #[debug(skip_fn = Vec::is_empty)]
+            skip_fn: Vec<u8>,
+            #[debug("<redacted>")]
+            bare: &'static str,
+            #[debug("{:#x}")]
+            interpolated: u32,
+            #[debug(wrapper = Wrapper)]
+            wrapper: u8,
+            #[debug(max_items = 2, alt_wrapper = ByteStr)]
+            alt_wrapper: Vec<u8>,
+            #[debug("{:.4}…", alt = "{}")]
+            alt: f64,
+            #[debug(head = 3, tail = 2)]
+            head_tail: Vec<u8>,
+            #[debug(hex)]
+            hex: [u8; 4],
+            #[debug(bytes)]
+            bytes: Vec<u8>,
+            #[debug(bytes_size)]
+            bytes_size: u64,
+            #[debug(duration)]
+            duration: Duration,
+            #[debug(unix_time = millis)]
+            unix_time: u64,
+            #[debug(lower_hex)]
+            lower_hex: u8,
+            #[debug(display)]
+            display: String,
+            #[debug(inline)]
+            inline: Vec<u8>,
+            #[debug(pretty_only)]
+            pretty_only: u8,
+            #[debug(compact_only, "<{} bytes>")]
+            compact_only: usize,
+            #[debug(level = 2)]
+            level: u8,
+            #[debug(rename = "type")]
+            renamed: u8,
+        }
+    }));
+}
+
+#[test]
+fn markers() {
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(exhaustive_marker = count)]
+        struct Count {
+            #[debug(skip_if = 0)]
+            a: u8,
+            b: u8,
+        }
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(exhaustive_marker = names)]
+        #[non_exhaustive]
+        struct Names {
+            #[debug(skip, rename = "cache_bytes")]
+            cache: Vec<u8>,
+        }
+    }));
+}
+
+#[test]
+fn tuple_attrs() {
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(skip_defaults, placeholder = "<default>")]
+        struct Placeholder(u8, #[debug(hex)] [u8; 2]);
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(skip_defaults, tuple_skip = omit, exhaustive_marker = count)]
+        struct Omit(u8, u8);
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(skip_defaults, tuple_skip = trailing)]
+        struct Trailing(u8, u8);
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(indexed)]
+        struct Indexed(u8, #[debug(skip)] u8);
+    }));
+}
+
+#[test]
+fn container_attrs() {
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug("<unit>")]
+        struct Bare;
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(skip)]
+        struct Skipped {
+            a: u8,
+        }
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(cycle_safe)]
+        struct Node {
+            edges: Vec<Rc<RefCell<Node>>>,
+        }
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(crate = facade::smart_debug)]
+        struct CratePath {
+            #[debug(level = 1)]
+            a: u8,
+        }
+    }));
+}
+
+#[test]
+fn enums_are_rejected() {
+    let input: DeriveInput = parse_quote! {
+        enum Either {
+            Left(u8),
+            Right(u8),
+        }
+    };
+    let err = crate::body_impl::impl_derive(&input, Target::Debug).unwrap_err();
+    assert_eq!(err.to_string(), "Only structs are currently supported");
+    let err = defmt_impl::impl_derive(&input).unwrap_err();
+    assert_eq!(err.to_string(), "Only structs are currently supported");
+}
+
+#[test]
+fn depth_tracking() {
+    crate::body_impl::TRACK_DEPTH.with(|track| track.set(true));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        struct Named {
+            a: u8,
+        }
+    }));
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(max_depth = 4)]
+        struct Ast(Vec<Expr>);
+    }));
+}
+
+#[test]
+fn max_depth_zero() {
+    let input = parse_quote! {
+        #[debug(max_depth = 0)]
+        struct Ast(Vec<Expr>);
+    };
+    let err = crate::body_impl::impl_derive(&input, Target::Debug).unwrap_err();
+    assert_eq!(err.to_string(), "`max_depth` must be at least 1");
+}
+
+#[test]
+fn views() {
+    insta::assert_snapshot!(expand_debug(parse_quote! {
+        #[debug(view(Dev, inline_fields))]
+        struct Viewed {
+            #[debug(view(Audit, "<redacted>"))]
+            token: String,
+            #[debug(skip, view(Dev, no_skip, lower_hex))]
+            flags: u8,
+        }
+    }));
+}
+
+#[test]
+fn display() {
+    insta::assert_snapshot!(expand_display(parse_quote! {
+        #[display("{user}@{host}")]
+        struct Template {
+            user: String,
+            #[display(skip_if = "localhost")]
+            host: &'static str,
+        }
+    }));
+    insta::assert_snapshot!(expand_display(parse_quote! {
+        struct Plain {
+            #[display(skip)]
+            a: u8,
+            b: u8,
+        }
+    }));
+    insta::assert_snapshot!(expand_display(parse_quote! {
+        #[display("{0:?} @ {1:#x} ({1})")]
+        struct Tagged<T, U>(T, U);
+    }));
+}
+
+#[test]
+fn defmt() {
+    insta::assert_snapshot!(pretty(
+        defmt_impl::impl_derive(&parse_quote! {
+            #[debug(skip_defaults)]
+            struct Named {
+                a: u8,
+                #[debug("<redacted>")]
+                b: u8,
+                #[debug(wrapper = Wrapper)]
+                c: u8,
+            }
+        })
+        .unwrap()
+    ));
+}
+
+#[test]
+fn valuable() {
+    insta::assert_snapshot!(pretty(
+        crate::valuable_impl::impl_derive(&parse_quote! {
+            struct Named {
+                #[debug(skip)]
+                a: u8,
+                #[debug("{:#x}")]
+                b: u8,
+            }
+        })
+        .unwrap()
+    ));
+}
+
+#[test]
+fn log_kv() {
+    insta::assert_snapshot!(pretty(
+        crate::log_kv_impl::impl_derive(&parse_quote! {
+            struct Named {
+                #[debug(skip_if = 0)]
+                a: u8,
+                #[debug("{:#x}")]
+                b: u8,
+            }
+        })
+        .unwrap()
+    ));
+}
@@ -1,3 +1,13 @@
+use crate::{
+    attr::{container, field},
+    body_impl::{Skip, Target},
+    view_impl,
+};
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{parse::Result, parse_quote, DeriveInput, Fields, Generics};
+
 /// Returns `true` is the string should be run through `format_args!()`
 pub fn needs_formatting(s: &str) -> bool {
     // We used to use more complex logic here, but it got hard to track both as authors and users
@@ -5,3 +15,172 @@ pub fn needs_formatting(s: &str) -> bool {
     // the user wants a literal curly then they can escape it
     s.contains(['{', '}'])
 }
+
+/// An arg that's referenced by a template e.g. `{_0:x}` has the name `_0` and the spec `x`
+pub struct TemplateArg {
+    pub name: String,
+    pub spec: String,
+}
+
+impl TemplateArg {
+    /// The `core::fmt` trait that the arg gets formatted with, going by the type at the end of its
+    /// spec
+    pub fn fmt_trait(&self) -> TokenStream {
+        match self.spec.chars().last() {
+            Some('?') => quote! { ::core::fmt::Debug },
+            Some('x') => quote! { ::core::fmt::LowerHex },
+            Some('X') => quote! { ::core::fmt::UpperHex },
+            Some('b') => quote! { ::core::fmt::Binary },
+            Some('o') => quote! { ::core::fmt::Octal },
+            Some('e') => quote! { ::core::fmt::LowerExp },
+            Some('E') => quote! { ::core::fmt::UpperExp },
+            Some('p') => quote! { ::core::fmt::Pointer },
+            _ => quote! { ::core::fmt::Display },
+        }
+    }
+}
+
+/// Returns the template with positional args renamed to named ones (`{0}` -> `{_0}`) along with
+/// all of the args that are referenced
+pub fn template_args(template: &str) -> (String, Vec<TemplateArg>) {
+    let mut renamed = String::with_capacity(template.len());
+    let mut args = Vec::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        renamed.push(c);
+        match c {
+            // Escaped braces
+            '{' | '}' if chars.peek() == Some(&c) => renamed.push(chars.next().unwrap()),
+            '{' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == ':' || c == '}' {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                // The spec gets copied over as is by the outer loop
+                let mut spec_chars = chars.clone();
+                let spec: String = match spec_chars.next() {
+                    Some(':') => spec_chars.take_while(|&c| c != '}').collect(),
+                    _ => String::new(),
+                };
+
+                let name = name.trim();
+                if !name.is_empty() && name.chars().all(|c| c.is_ascii_digit()) {
+                    renamed.push('_');
+                    args.push(TemplateArg {
+                        name: format!("_{name}"),
+                        spec,
+                    });
+                } else if !name.is_empty() {
+                    args.push(TemplateArg {
+                        name: name.to_owned(),
+                        spec,
+                    });
+                }
+                renamed.push_str(name);
+            }
+            _ => {}
+        }
+    }
+
+    (renamed, args)
+}
+
+/// The fields of the struct that's being derived for
+pub fn struct_fields(input: &DeriveInput) -> Result<&Fields> {
+    match &input.data {
+        syn::Data::Struct(body) => Ok(&body.fields),
+        _ => Err(syn::Error::new_spanned(
+            input,
+            "Only structs are currently supported",
+        )),
+    }
+}
+
+/// Bounds each type param that shows up in a field that's formatted as is by `bound` just like
+/// std's derives do e.g. `T: ::core::fmt::Debug`
+///
+/// Fields that are always skipped or that have a custom representation are never formatted with
+/// `bound`, so their type params are left for the user to bound if anything else needs it. Fields
+/// referenced by a `Display` template get bounded by whichever trait their spec formats them with
+/// instead e.g. `{0:?}` -> `::core::fmt::Debug`
+pub fn with_bounds(input: &DeriveInput, target: Target, bound: &TokenStream) -> Result<Generics> {
+    let fields = struct_fields(input)?;
+    let namespace = target.namespace();
+
+    // Views can format fields that are otherwise skipped
+    let mut views = vec![None];
+    if target == Target::Debug {
+        views.extend(view_impl::views(input)?.into_iter().map(Some));
+    }
+
+    let mut formatted_types = Vec::new();
+    for view in &views {
+        let container = container::Attrs::parse(&input.attrs, namespace)?.in_view(view.as_ref())?;
+        // Display templates only format the fields that they reference
+        let template_args = match &container.bare {
+            Some(lit_str) if target == Target::Display && needs_formatting(&lit_str.value()) => {
+                Some(template_args(&lit_str.value()).1)
+            }
+            Some(_) => continue,
+            None => None,
+        };
+
+        for (field_num, field) in fields.iter().enumerate() {
+            let attrs = field::Attrs::parse(&field.attrs, namespace)?.in_view(view.as_ref())?;
+            if attrs.repr.is_some() {
+                continue;
+            }
+            let ty = field.ty.to_token_stream();
+            match &template_args {
+                Some(args) => {
+                    let arg_name = match &field.ident {
+                        Some(ident) => ident.to_string(),
+                        None => format!("_{field_num}"),
+                    };
+                    for arg in args.iter().filter(|arg| arg.name == arg_name) {
+                        formatted_types.push((ty.clone(), arg.fmt_trait()));
+                    }
+                }
+                None => {
+                    if !Skip::new(&container.skip, attrs.skip).is_unconditional() {
+                        formatted_types.push((ty, bound.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut generics = input.generics.clone();
+    let mut predicates: Vec<(Ident, TokenStream)> = Vec::new();
+    for param in generics.type_params() {
+        for (ty, bound) in &formatted_types {
+            let is_new = !predicates.iter().any(|(other_param, other_bound)| {
+                *other_param == param.ident && other_bound.to_string() == bound.to_string()
+            });
+            if is_new && mentions(ty, &param.ident) {
+                predicates.push((param.ident.clone(), bound.clone()));
+            }
+        }
+    }
+    let where_clause = generics.make_where_clause();
+    for (param, bound) in predicates {
+        where_clause
+            .predicates
+            .push(parse_quote! { #param: #bound });
+    }
+
+    Ok(generics)
+}
+
+/// Whether the `ident` shows up anywhere within the `tokens`
+fn mentions(tokens: &TokenStream, ident: &Ident) -> bool {
+    tokens.clone().into_iter().any(|tt| match tt {
+        TokenTree::Ident(other) => other == *ident,
+        TokenTree::Group(group) => mentions(&group.stream(), ident),
+        _ => false,
+    })
+}
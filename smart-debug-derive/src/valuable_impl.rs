@@ -0,0 +1,158 @@
+use crate::{
+    attr::{container, field},
+    body_impl::{self, Skip, Target},
+    utils,
+};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, parse::Result, DeriveInput, Fields};
+
+/// Generates `valuable::Valuable` and `valuable::Structable` impls that only visit the fields that
+/// would be displayed by the `Debug` impl
+///
+/// Fields with a custom representation get visited as their formatted `Debug` str, so redacted
+/// values never make it into the structured output
+pub fn impl_derive(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let name_lit_str = name.unraw().to_string();
+    let container = container::Attrs::parse(&input.attrs, Target::Debug.namespace())?;
+    let krate = body_impl::crate_tt(container.crate_path);
+    let valuable = quote! { #krate::internal::valuable };
+    let generics = utils::with_bounds(input, Target::Debug, &quote! { #valuable::Valuable })?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if let Some(lit_str) = container.bare {
+        return Ok(quote! {
+            impl #impl_generics #valuable::Valuable for #name #ty_generics #where_clause {
+                fn as_value(&self) -> #valuable::Value<'_> {
+                    #valuable::Value::String(#lit_str)
+                }
+
+                fn visit(&self, visit: &mut dyn #valuable::Visit) {
+                    visit.visit_value(self.as_value());
+                }
+            }
+        });
+    }
+
+    let fields = utils::struct_fields(input)?;
+
+    let container_defaults = body_impl::container_defaults_tt(name, &container.skip);
+
+    let mut preludes = Vec::new();
+    let mut named_visits = Vec::new();
+    let mut unnamed_values = Vec::new();
+    for (field_num, field) in fields.iter().enumerate() {
+        let member = body_impl::field_member(field_num, field);
+        let field::Attrs {
+            repr,
+            alt_repr: _,
+            skip: field_skip,
+            only,
+            level,
+            inline: _,
+            rename,
+            views: _,
+        } = field::Attrs::parse(&field.attrs, Target::Debug.namespace())?;
+        let skip = Skip::new(&container.skip, field_skip);
+        if skip.is_unconditional() {
+            if field.ident.is_none() {
+                unnamed_values.push(quote! { #valuable::Value::Unit });
+            }
+            continue;
+        }
+        let cond = body_impl::hidden_cond_tt(
+            skip.cond_tt(&member, &field.ty),
+            only,
+            level,
+            &quote! { false },
+            &krate,
+        )
+        .unwrap_or_else(|| quote! { false });
+
+        let value = match repr {
+            None => quote! { #valuable::Valuable::as_value(&self.#member) },
+            // Non-interpolated strs can be used directly
+            Some(field::Repr::Bare(bare)) if !utils::needs_formatting(&bare.value()) => {
+                quote! { #valuable::Value::String(#bare) }
+            }
+            Some(repr) => {
+                let formatted = format_ident!("field_{}_formatted", field_num);
+                let value =
+                    body_impl::value_tt(Some(repr), None, false, &member, Target::Debug, &krate);
+                preludes.push(quote! {
+                    let #formatted = #krate::internal::format!("{:?}", #value);
+                });
+                quote! { #valuable::Value::String(&#formatted) }
+            }
+        };
+
+        match &field.ident {
+            Some(_) => {
+                let label = body_impl::field_label(field_num, field, rename);
+                named_visits.push(quote! {
+                    if !(#cond) {
+                        visit.visit_named_fields(&#valuable::NamedValues::new(
+                            &[#valuable::NamedField::new(#label)],
+                            &[#value],
+                        ));
+                    }
+                });
+            }
+            // Skipped tuple fields are kept as placeholders just like the `_` from `Debug`
+            None => unnamed_values.push(quote! {
+                if #cond { #valuable::Value::Unit } else { #value }
+            }),
+        }
+    }
+
+    let visit_body = match fields {
+        Fields::Named(_) => quote! {
+            #container_defaults
+            #( #preludes )*
+            #( #named_visits )*
+        },
+        Fields::Unnamed(_) => quote! {
+            #container_defaults
+            #( #preludes )*
+            visit.visit_unnamed_fields(&[#( #unnamed_values ),*]);
+        },
+        Fields::Unit => TokenStream::new(),
+    };
+
+    let struct_def = match fields {
+        Fields::Named(_) => quote! {
+            #valuable::StructDef::new_dynamic(#name_lit_str, #valuable::Fields::Named(&[]))
+        },
+        Fields::Unnamed(unnamed) => {
+            let len = unnamed.unnamed.len();
+            quote! {
+                #valuable::StructDef::new_static(#name_lit_str, #valuable::Fields::Unnamed(#len))
+            }
+        }
+        Fields::Unit => quote! {
+            #valuable::StructDef::new_static(#name_lit_str, #valuable::Fields::Unnamed(0))
+        },
+    };
+
+    let valuable_impl = quote! {
+        impl #impl_generics #valuable::Valuable for #name #ty_generics #where_clause {
+            fn as_value(&self) -> #valuable::Value<'_> {
+                #valuable::Value::Structable(self)
+            }
+
+            fn visit(&self, visit: &mut dyn #valuable::Visit) {
+                #visit_body
+            }
+        }
+
+        impl #impl_generics #valuable::Structable for #name #ty_generics #where_clause {
+            fn definition(&self) -> #valuable::StructDef<'_> {
+                #struct_def
+            }
+        }
+    };
+
+    Ok(valuable_impl)
+}
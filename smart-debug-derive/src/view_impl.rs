@@ -0,0 +1,72 @@
+use crate::{
+    attr::{self, container, field},
+    body_impl::{self, Target},
+    utils,
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Result, DeriveInput, Path};
+
+/// Generates a `DebugView` impl for each named view that follows the `Debug` impl's attrs with the
+/// view's attrs taking precedence
+pub fn impl_derive(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let generics = utils::with_bounds(input, Target::Debug, &quote! { ::core::fmt::Debug })?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let krate = crate_tt(input)?;
+
+    let mut output = TokenStream::new();
+    for view in views(input)? {
+        let fn_body = body_impl::fmt_body_tt(input, Target::Debug, Some(&view))?;
+        output.extend(quote! {
+            impl #impl_generics #krate::DebugView<#view> for #name #ty_generics #where_clause {
+                fn fmt_view(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #fn_body
+                }
+            }
+        });
+    }
+
+    Ok(output)
+}
+
+/// Switches the `Debug` impl over to the view that's currently being formatted if the type has it
+pub fn dispatch_tt(input: &DeriveInput) -> Result<TokenStream> {
+    let krate = crate_tt(input)?;
+    let dispatches = views(input)?.into_iter().map(|view| {
+        quote! {
+            if #krate::internal::__is_view::<#view>() {
+                return <Self as #krate::DebugView<#view>>::fmt_view(self, f);
+            }
+        }
+    });
+
+    Ok(quote! { #( #dispatches )* })
+}
+
+fn crate_tt(input: &DeriveInput) -> Result<TokenStream> {
+    let container = container::Attrs::parse(&input.attrs, Target::Debug.namespace())?;
+    Ok(body_impl::crate_tt(container.crate_path))
+}
+
+/// All of the distinct views named on the container or any of its fields
+pub fn views(input: &DeriveInput) -> Result<Vec<Path>> {
+    let namespace = Target::Debug.namespace();
+    let container = container::Attrs::parse(&input.attrs, namespace)?;
+    let mut views: Vec<Path> = container.views.into_iter().map(|view| view.name).collect();
+
+    let fields = utils::struct_fields(input)?;
+    for field in fields {
+        for view in field::Attrs::parse(&field.attrs, namespace)?.views {
+            let is_new = views
+                .iter()
+                .all(|other| !attr::is_same_path(other, &view.name));
+            if is_new {
+                views.push(view.name);
+            }
+        }
+    }
+
+    Ok(views)
+}
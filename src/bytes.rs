@@ -0,0 +1,82 @@
+use core::fmt::{self, Write};
+
+const ROW_LEN: usize = 16;
+
+/// Formats a byte buffer as hex
+///
+/// Compact formatting (`{:?}`) displays it as a single number like `0x48656c6c6f` while pretty
+/// formatting (`{:#?}`) displays a classic offset/hex/ASCII dump. An empty buffer is displayed as
+/// `[]` either way since a bare `0x` isn't a number
+///
+/// This is what `#[debug(hex)]` uses
+pub struct Hex<'inner, T: ?Sized>(pub &'inner T);
+
+impl<T: AsRef<[u8]> + ?Sized> fmt::Debug for Hex<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0.as_ref();
+        if f.alternate() || bytes.is_empty() {
+            hex_dump(f, bytes)
+        } else {
+            f.write_str("0x")?;
+            bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+        }
+    }
+}
+
+fn hex_dump(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    if bytes.is_empty() {
+        return f.write_str("[]");
+    }
+
+    f.write_str("[\n")?;
+    for (row_num, row) in bytes.chunks(ROW_LEN).enumerate() {
+        write!(f, "    {:08x} ", row_num * ROW_LEN)?;
+        for i in 0..ROW_LEN {
+            // Extra space between the two halves of the row
+            if i % (ROW_LEN / 2) == 0 {
+                f.write_char(' ')?;
+            }
+
+            match row.get(i) {
+                Some(byte) => write!(f, "{byte:02x} ")?,
+                None => f.write_str("   ")?,
+            }
+        }
+
+        f.write_str(" |")?;
+        for &byte in row {
+            let c = if byte.is_ascii_graphic() || byte == b' ' {
+                char::from(byte)
+            } else {
+                '.'
+            };
+            f.write_char(c)?;
+        }
+        f.write_str("|\n")?;
+    }
+    f.write_str("]")
+}
+
+/// Formats a byte buffer as an escaped byte string like `b"Hello\x00"`
+///
+/// This is what `#[debug(bytes)]` uses
+pub struct ByteStr<'inner, T: ?Sized>(pub &'inner T);
+
+impl<T: AsRef<[u8]> + ?Sized> fmt::Debug for ByteStr<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("b\"")?;
+        for &byte in self.0.as_ref() {
+            match byte {
+                b'"' => f.write_str("\\\"")?,
+                b'\\' => f.write_str("\\\\")?,
+                b'\n' => f.write_str("\\n")?,
+                b'\r' => f.write_str("\\r")?,
+                b'\t' => f.write_str("\\t")?,
+                b' ' => f.write_char(' ')?,
+                _ if byte.is_ascii_graphic() => f.write_char(char::from(byte))?,
+                _ => write!(f, "\\x{byte:02x}")?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
@@ -0,0 +1,82 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use {
+    core::{any, cell::RefCell},
+    std::{thread_local, vec::Vec},
+};
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// The `cycle_safe` values that are currently being formatted further up the stack
+    static VISITING: RefCell<Vec<Visit>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A value is identified by its address along with its type since a struct shares its address
+/// with its first field
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Visit {
+    addr: usize,
+    type_name: &'static str,
+}
+
+#[cfg(feature = "std")]
+impl Visit {
+    fn new<T: ?Sized>(value: &T) -> Self {
+        Self {
+            addr: value as *const T as *const () as usize,
+            type_name: any::type_name::<T>(),
+        }
+    }
+}
+
+/// Marks the value as being formatted until it's dropped
+#[cfg(feature = "std")]
+pub struct VisitGuard(Visit);
+
+#[cfg(feature = "std")]
+impl VisitGuard {
+    /// Returns `None` when the value is already being formatted i.e. it's part of a cycle
+    pub fn enter<T: ?Sized>(value: &T) -> Option<Self> {
+        let visit = Visit::new(value);
+        VISITING.with(|visiting| {
+            let mut visiting = visiting.borrow_mut();
+            if visiting.contains(&visit) {
+                None
+            } else {
+                visiting.push(visit);
+                Some(Self(visit))
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for VisitGuard {
+    fn drop(&mut self) {
+        VISITING.with(|visiting| {
+            let mut visiting = visiting.borrow_mut();
+            if let Some(pos) = visiting.iter().rposition(|visit| *visit == self.0) {
+                visiting.remove(pos);
+            }
+        });
+    }
+}
+
+/// Displayed in place of a value that's already being formatted e.g. `<cycle: Node@0x1234>`
+pub struct Cycle<'value, T: ?Sized> {
+    name: &'static str,
+    value: &'value T,
+}
+
+impl<'value, T: ?Sized> Cycle<'value, T> {
+    pub fn new(name: &'static str, value: &'value T) -> Self {
+        Self { name, value }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Cycle<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<cycle: {}@{:p}>", self.name, self.value)
+    }
+}
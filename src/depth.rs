@@ -0,0 +1,89 @@
+use core::{cell::Cell, fmt};
+use std::thread_local;
+
+thread_local! {
+    /// How many `SmartDebug` values are currently being formatted
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// The deepest that values get expanded to before they're collapsed to `Name { .. }`
+    static LIMIT: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+/// Formats the value with `SmartDebug` values nested more than `max_depth` levels deep collapsed
+///
+/// The value itself is the first level. Anything past the limit is displayed as `Name { .. }` or
+/// `Name(..)` instead of being expanded. Every `SmartDebug` value tracks its depth when the `depth`
+/// feature is enabled, including types without any attrs, so they all get collapsed
+///
+/// ```
+/// use smart_debug::SmartDebug;
+///
+/// #[derive(SmartDebug)]
+/// struct Node {
+///     value: u8,
+///     next: Option<Box<Node>>,
+/// }
+///
+/// let list = Node { value: 1, next: Some(Box::new(Node { value: 2, next: None })) };
+/// assert_eq!(
+///     format!("{:?}", smart_debug::depth_limited(&list, 1)),
+///     "Node { value: 1, next: Some(Node { .. }) }",
+/// );
+/// ```
+pub fn depth_limited<T: ?Sized>(value: &T, max_depth: usize) -> DepthLimited<'_, T> {
+    DepthLimited {
+        inner: value,
+        max_depth,
+    }
+}
+
+/// The adapter returned from [`depth_limited()`]
+pub struct DepthLimited<'inner, T: ?Sized> {
+    inner: &'inner T,
+    max_depth: usize,
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for DepthLimited<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = DEPTH.with(Cell::get).saturating_add(self.max_depth);
+        with_limit(limit, || self.inner.fmt(f))
+    }
+}
+
+fn with_limit<R>(limit: usize, func: impl FnOnce() -> R) -> R {
+    let limit = LIMIT.with(Cell::get).min(limit);
+    crate::scoped::with_thread_local(&LIMIT, limit, func)
+}
+
+/// Marks a `SmartDebug` value as being formatted one level deeper until it's dropped
+pub struct DepthGuard {
+    prev_limit: usize,
+}
+
+impl DepthGuard {
+    /// Returns `None` when the value is past the depth limit and should be collapsed
+    pub fn enter() -> Option<Self> {
+        let depth = DEPTH.with(Cell::get) + 1;
+        let limit = LIMIT.with(Cell::get);
+        if depth > limit {
+            return None;
+        }
+
+        DEPTH.with(|current| current.set(depth));
+        Some(Self { prev_limit: limit })
+    }
+
+    /// Limits the values nested within to `max_depth` levels with the current value as the first
+    pub fn limit(&self, max_depth: usize) {
+        let limit = DEPTH
+            .with(Cell::get)
+            .saturating_add(max_depth.saturating_sub(1));
+        LIMIT.with(|current| current.set(current.get().min(limit)));
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|current| current.set(current.get() - 1));
+        LIMIT.with(|current| current.set(self.prev_limit));
+    }
+}
@@ -0,0 +1,51 @@
+use core::fmt;
+
+/// Visits the fields that would be displayed by a type's `SmartDebug` impl
+///
+/// This is implemented by the `SmartDebug` derive. Each displayed field is visited with its label
+/// and its value, as it would be formatted, followed by the number of fields that were skipped
+pub trait DebugFields {
+    fn visit(&self, visitor: &mut impl FieldVisitor);
+}
+
+/// An object safe version of [`DebugFields`] that's implemented for all `DebugFields` types
+pub trait DynDebugFields {
+    fn visit_dyn(&self, visitor: &mut dyn FieldVisitor);
+}
+
+impl<T: DebugFields + ?Sized> DynDebugFields for T {
+    fn visit_dyn(&self, visitor: &mut dyn FieldVisitor) {
+        self.visit(&mut DynVisitor(visitor));
+    }
+}
+
+struct DynVisitor<'visitor>(&'visitor mut dyn FieldVisitor);
+
+impl FieldVisitor for DynVisitor<'_> {
+    fn visit_field(&mut self, label: &str, value: &dyn fmt::Debug) {
+        self.0.visit_field(label, value);
+    }
+
+    fn visit_nested(&mut self, label: &str, value: &dyn fmt::Debug, fields: &dyn DynDebugFields) {
+        self.0.visit_nested(label, value, fields);
+    }
+
+    fn visit_skipped(&mut self, count: usize) {
+        self.0.visit_skipped(count);
+    }
+}
+
+/// Receives the fields from [`DebugFields::visit()`]
+pub trait FieldVisitor {
+    /// Called for each displayed field in order. Tuple struct fields are labeled by their index
+    fn visit_field(&mut self, label: &str, value: &dyn fmt::Debug);
+
+    /// Called instead of [`FieldVisitor::visit_field()`] for fields that are displayed as is and
+    /// also implement [`DebugFields`]
+    fn visit_nested(&mut self, label: &str, value: &dyn fmt::Debug, _fields: &dyn DynDebugFields) {
+        self.visit_field(label, value);
+    }
+
+    /// Called once after all the fields with the number of fields that were skipped
+    fn visit_skipped(&mut self, _count: usize) {}
+}
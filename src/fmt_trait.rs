@@ -0,0 +1,47 @@
+use core::fmt;
+
+macro_rules! fmt_trait_wrapper {
+    ($(#[$meta:meta])* $name:ident, $fmt_trait:ident, $attr:literal) => {
+        $(#[$meta])*
+        ///
+        /// The formatter gets forwarded as is, so things like padding and precision are respected
+        ///
+        #[doc = concat!("This is what `#[debug(", $attr, ")]` uses")]
+        pub struct $name<'inner, T: ?Sized>(pub &'inner T);
+
+        impl<T: fmt::$fmt_trait + ?Sized> fmt::Debug for $name<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::$fmt_trait::fmt(self.0, f)
+            }
+        }
+    };
+}
+
+fmt_trait_wrapper!(
+    /// Formats the value with [`fmt::Display`]
+    AsDisplay, Display, "display"
+);
+fmt_trait_wrapper!(
+    /// Formats the value with [`fmt::LowerHex`]
+    AsLowerHex, LowerHex, "lower_hex"
+);
+fmt_trait_wrapper!(
+    /// Formats the value with [`fmt::UpperHex`]
+    AsUpperHex, UpperHex, "upper_hex"
+);
+fmt_trait_wrapper!(
+    /// Formats the value with [`fmt::Binary`]
+    AsBinary, Binary, "binary"
+);
+fmt_trait_wrapper!(
+    /// Formats the value with [`fmt::Octal`]
+    AsOctal, Octal, "octal"
+);
+fmt_trait_wrapper!(
+    /// Formats the value with [`fmt::LowerExp`]
+    AsLowerExp, LowerExp, "lower_exp"
+);
+fmt_trait_wrapper!(
+    /// Formats the value with [`fmt::UpperExp`]
+    AsUpperExp, UpperExp, "upper_exp"
+);
@@ -0,0 +1,12 @@
+use core::fmt;
+
+/// Wrapper that displays inline even when using pretty formatting (`{:#?}`)
+///
+/// This is what `#[debug(inline)]` uses
+pub struct DebugInline<'inner, T: ?Sized>(pub &'inner T);
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for DebugInline<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.0))
+    }
+}
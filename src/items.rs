@@ -0,0 +1,186 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+use crate::internal::__LiteralField;
+
+/// Formats a collection while only showing the first `head` and last `tail` items
+///
+/// Everything in between is replaced with a single `...` entry and the full length gets appended
+/// after the collection, so `[1, 2, 3, ..., 99, 100] (len = 100)` for a `head` of 3 and a `tail` of
+/// 2. Collections that are short enough to be displayed in full are formatted as usual.
+///
+/// This is what `#[debug(max_items = ...)]` and `#[debug(head = ..., tail = ...)]` use
+pub struct Elided<'inner, T: ?Sized> {
+    inner: &'inner T,
+    head: usize,
+    tail: usize,
+}
+
+impl<'inner, T: ?Sized> Elided<'inner, T> {
+    pub fn new(inner: &'inner T, head: usize, tail: usize) -> Self {
+        Self { inner, head, tail }
+    }
+}
+
+impl<T: Items + ?Sized> fmt::Debug for Elided<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt_elided(f, self.head, self.tail)
+    }
+}
+
+/// Collections that can be formatted through [`Elided`]
+pub trait Items {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result;
+}
+
+impl<T: Items + ?Sized> Items for &T {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        (**self).fmt_elided(f, head, tail)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Items + ?Sized> Items for Box<T> {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        (**self).fmt_elided(f, head, tail)
+    }
+}
+
+impl<T: fmt::Debug> Items for [T] {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        fmt_list(f, self.iter(), head, tail)
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> Items for [T; N] {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        fmt_list(f, self.iter(), head, tail)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> Items for Vec<T> {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        fmt_list(f, self.iter(), head, tail)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> Items for VecDeque<T> {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        fmt_list(f, self.iter(), head, tail)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug, S> Items for HashSet<T, S> {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        fmt_set(f, self.iter(), head, tail)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> Items for BTreeSet<T> {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        fmt_set(f, self.iter(), head, tail)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, V: fmt::Debug, S> Items for HashMap<K, V, S> {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        let entries = self.iter().map(|(key, value)| MapEntry(key, value));
+        fmt_set(f, entries, head, tail)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: fmt::Debug, V: fmt::Debug> Items for BTreeMap<K, V> {
+    fn fmt_elided(&self, f: &mut fmt::Formatter<'_>, head: usize, tail: usize) -> fmt::Result {
+        let entries = self.iter().map(|(key, value)| MapEntry(key, value));
+        fmt_set(f, entries, head, tail)
+    }
+}
+
+fn fmt_list<I>(f: &mut fmt::Formatter<'_>, items: I, head: usize, tail: usize) -> fmt::Result
+where
+    I: ExactSizeIterator,
+    I::Item: fmt::Debug,
+{
+    let len = items.len();
+    let mut list = f.debug_list();
+    let elided = push_entries(items, head, tail, |entry| {
+        list.entry(entry);
+    });
+    list.finish()?;
+    write_len(f, elided, len)
+}
+
+// Maps go through `debug_set()` with entries that format as `key: value` since `debug_map()` has no
+// way to display a lone `...` entry. The output is identical to `debug_map()` otherwise
+#[cfg(feature = "alloc")]
+fn fmt_set<I>(f: &mut fmt::Formatter<'_>, items: I, head: usize, tail: usize) -> fmt::Result
+where
+    I: ExactSizeIterator,
+    I::Item: fmt::Debug,
+{
+    let len = items.len();
+    let mut set = f.debug_set();
+    let elided = push_entries(items, head, tail, |entry| {
+        set.entry(entry);
+    });
+    set.finish()?;
+    write_len(f, elided, len)
+}
+
+/// Pushes all of the displayed entries and returns whether any were elided
+fn push_entries<I>(
+    mut items: I,
+    head: usize,
+    tail: usize,
+    mut push: impl FnMut(&dyn fmt::Debug),
+) -> bool
+where
+    I: ExactSizeIterator,
+    I::Item: fmt::Debug,
+{
+    let len = items.len();
+    if len <= head.saturating_add(tail) {
+        items.for_each(|item| push(&item));
+        return false;
+    }
+
+    items.by_ref().take(head).for_each(|item| push(&item));
+    push(&__LiteralField(format_args!("...")));
+    items.skip(len - head - tail).for_each(|item| push(&item));
+
+    true
+}
+
+fn write_len(f: &mut fmt::Formatter<'_>, elided: bool, len: usize) -> fmt::Result {
+    if elided {
+        write!(f, " (len = {len})")
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct MapEntry<K, V>(K, V);
+
+#[cfg(feature = "alloc")]
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for MapEntry<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)?;
+        f.write_str(": ")?;
+        self.1.fmt(f)
+    }
+}
@@ -0,0 +1,144 @@
+use core::fmt::{self, Write};
+
+use crate::{DebugFields, DynDebugFields, FieldVisitor};
+
+/// Renders the fields that a `SmartDebug` type displays as a JSON object
+///
+/// Fields that also derive `SmartDebug` are rendered as nested objects while everything else is
+/// rendered as a string of its formatted `Debug` value. Skipped fields are left out entirely, so
+/// skipped and redacted values are never exposed. Use `{:#}` for pretty printed output
+///
+/// ```
+/// use smart_debug::SmartDebug;
+///
+/// #[derive(SmartDebug)]
+/// struct User {
+///     name: &'static str,
+///     #[debug("<redacted>")]
+///     password: &'static str,
+/// }
+///
+/// let user = User { name: "ferris", password: "hunter2" };
+/// assert_eq!(
+///     smart_debug::json(&user).to_string(),
+///     r#"{"name":"\"ferris\"","password":"<redacted>"}"#,
+/// );
+/// ```
+pub fn json<T: DebugFields + ?Sized>(value: &T) -> Json<'_, T> {
+    Json(value)
+}
+
+/// The adapter returned from [`json()`]
+pub struct Json<'inner, T: ?Sized>(&'inner T);
+
+impl<T: DebugFields + ?Sized> fmt::Display for Json<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_object(f, self.0, 0)
+    }
+}
+
+fn write_object<T: DynDebugFields + ?Sized>(
+    f: &mut fmt::Formatter<'_>,
+    fields: &T,
+    depth: usize,
+) -> fmt::Result {
+    f.write_char('{')?;
+    let mut object = JsonObject {
+        f,
+        depth,
+        is_empty: true,
+        result: Ok(()),
+    };
+    fields.visit_dyn(&mut object);
+    let JsonObject {
+        f,
+        is_empty,
+        result,
+        ..
+    } = object;
+    result?;
+
+    if !is_empty {
+        newline(f, depth)?;
+    }
+    f.write_char('}')
+}
+
+// Only does anything when pretty printing
+fn newline(f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+    if f.alternate() {
+        f.write_char('\n')?;
+        for _ in 0..depth {
+            f.write_str("  ")?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes each visited field as a member of a JSON object
+///
+/// Visitors can't return errors, so the first one gets stashed and everything after is ignored
+struct JsonObject<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+    depth: usize,
+    is_empty: bool,
+    result: fmt::Result,
+}
+
+impl JsonObject<'_, '_> {
+    fn write_key(&mut self, label: &str) -> fmt::Result {
+        if !self.is_empty {
+            self.f.write_char(',')?;
+        }
+        self.is_empty = false;
+        newline(self.f, self.depth + 1)?;
+
+        write_str_lit(self.f, format_args!("{label}"))?;
+        self.f
+            .write_str(if self.f.alternate() { ": " } else { ":" })
+    }
+}
+
+impl FieldVisitor for JsonObject<'_, '_> {
+    fn visit_field(&mut self, label: &str, value: &dyn fmt::Debug) {
+        if self.result.is_ok() {
+            self.result = self
+                .write_key(label)
+                .and_then(|()| write_str_lit(self.f, format_args!("{value:?}")));
+        }
+    }
+
+    fn visit_nested(&mut self, label: &str, _value: &dyn fmt::Debug, fields: &dyn DynDebugFields) {
+        if self.result.is_ok() {
+            self.result = self
+                .write_key(label)
+                .and_then(|()| write_object(self.f, fields, self.depth + 1));
+        }
+    }
+}
+
+/// Writes the formatted args as a JSON string literal
+fn write_str_lit(f: &mut fmt::Formatter<'_>, args: fmt::Arguments<'_>) -> fmt::Result {
+    f.write_char('"')?;
+    JsonEscaper(f).write_fmt(args)?;
+    f.write_char('"')
+}
+
+struct JsonEscaper<'a, 'f>(&'a mut fmt::Formatter<'f>);
+
+impl Write for JsonEscaper<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                c if u32::from(c) < 0x20 => write!(self.0, "\\u{:04x}", u32::from(c))?,
+                c => self.0.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -5,21 +5,112 @@
 //       sense
 // TODO: Get wrapper working as a container attr
 #![doc = include_str!("../README.md")]
+#![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[doc(inline)]
+pub use smart_debug_derive::{SmartDebug, SmartDisplay};
+
+#[cfg(feature = "defmt")]
+#[doc(inline)]
+pub use smart_debug_derive::SmartDefmt;
+
+#[cfg(feature = "valuable")]
+#[doc(inline)]
+pub use smart_debug_derive::SmartValuable;
+
+#[cfg(feature = "log-kv")]
 #[doc(inline)]
-pub use smart_debug_derive::SmartDebug;
+pub use smart_debug_derive::SmartLogKv;
+
+mod bytes;
+mod cycle;
+#[cfg(feature = "depth")]
+mod depth;
+mod fields;
+mod fmt_trait;
+mod inline;
+mod items;
+#[cfg(feature = "json")]
+mod json;
+mod marker;
+#[cfg(feature = "std")]
+mod scoped;
+mod units;
+#[cfg(feature = "std")]
+mod verbosity;
+mod view;
+
+pub use bytes::{ByteStr, Hex};
+#[cfg(feature = "depth")]
+pub use depth::{depth_limited, DepthLimited};
+pub use fields::{DebugFields, DynDebugFields, FieldVisitor};
+pub use fmt_trait::{AsBinary, AsDisplay, AsLowerExp, AsLowerHex, AsOctal, AsUpperExp, AsUpperHex};
+pub use inline::DebugInline;
+pub use items::{Elided, Items};
+#[cfg(feature = "json")]
+pub use json::{json, Json};
+pub use units::{ByteSize, HumanDuration, Integer, TimeUnit, UnixTime};
+#[cfg(feature = "std")]
+pub use verbosity::{verbose, Verbose};
+pub use view::{DebugView, DebugViewExt, View};
 
 /// NOT PART OF THE PUBLIC API
 #[doc(hidden)]
 pub mod internal {
-    use std::fmt;
+    use core::fmt;
+
+    use crate::{DebugFields, DynDebugFields};
+
+    pub use crate::cycle::Cycle as __Cycle;
+    #[cfg(feature = "std")]
+    pub use crate::cycle::VisitGuard as __VisitGuard;
+    #[cfg(feature = "depth")]
+    pub use crate::depth::DepthGuard as __DepthGuard;
+    pub use crate::marker::{
+        DebugStruct as __DebugStruct, DebugTuple as __DebugTuple, HiddenCount as __HiddenCount,
+        HiddenNames as __HiddenNames,
+    };
+    #[cfg(feature = "valuable")]
+    pub use alloc::format;
+    #[cfg(feature = "log-kv")]
+    pub use log;
+    #[cfg(feature = "valuable")]
+    pub use valuable;
+
+    /// Always `0` without `std` since there's no way to set it
+    pub fn __verbosity() -> u8 {
+        #[cfg(feature = "std")]
+        return crate::verbosity::current();
+        #[cfg(not(feature = "std"))]
+        return 0;
+    }
+
+    /// Cycles can't be detected without `std`, so every value is formatted like usual
+    #[cfg(not(feature = "std"))]
+    pub struct __VisitGuard;
 
-    pub struct __SkippedTupleField;
+    #[cfg(not(feature = "std"))]
+    impl __VisitGuard {
+        pub fn enter<T: ?Sized>(_value: &T) -> Option<Self> {
+            Some(Self)
+        }
+    }
 
-    impl fmt::Debug for __SkippedTupleField {
+    pub fn __is_view<V: ?Sized + 'static>() -> bool {
+        crate::view::is_current::<V>()
+    }
+
+    /// Displays the placeholder text for a skipped tuple field e.g. `_`
+    pub struct __SkippedTupleField<'text>(pub &'text str);
+
+    impl fmt::Debug for __SkippedTupleField<'_> {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // Specifics of the formatting target shouldn't matter here since it's always `_`
-            write!(f, "{:?}", __LiteralField(format_args!("_")))
+            f.write_str(self.0)
         }
     }
 
@@ -30,4 +121,117 @@ pub mod internal {
             f.write_fmt(self.0)
         }
     }
+
+    pub struct __DebugAsDisplay<'inner, T: ?Sized>(pub &'inner T);
+
+    impl<T: fmt::Debug + ?Sized> fmt::Display for __DebugAsDisplay<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(self.0, f)
+        }
+    }
+
+    impl<T: fmt::Debug + ?Sized> fmt::Debug for __DebugAsDisplay<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(self.0, f)
+        }
+    }
+
+    /// A field with a separate representation for when it's formatted in alternate mode e.g. `{:#?}`
+    pub struct __AltField<'inner>(pub &'inner dyn fmt::Debug, pub &'inner dyn fmt::Debug);
+
+    impl fmt::Debug for __AltField<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if f.alternate() {
+                self.1.fmt(f)
+            } else {
+                self.0.fmt(f)
+            }
+        }
+    }
+
+    /// Allows for borrowing a field's custom representation for as long as the container itself
+    ///
+    /// `N` is the index of the field
+    #[cfg(feature = "log-kv")]
+    #[repr(transparent)]
+    pub struct __FieldRef<T: ?Sized, const N: usize>(T);
+
+    #[cfg(feature = "log-kv")]
+    impl<T: ?Sized, const N: usize> __FieldRef<T, N> {
+        pub fn new(container: &T) -> &Self {
+            // SAFETY: `__FieldRef` is `#[repr(transparent)]` over `T`
+            unsafe { &*(container as *const T as *const Self) }
+        }
+    }
+
+    #[cfg(feature = "log-kv")]
+    impl<T: __FmtField<N> + ?Sized, const N: usize> fmt::Debug for __FieldRef<T, N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt_field(f)
+        }
+    }
+
+    /// Formats the `N`th field with its custom representation
+    #[cfg(feature = "log-kv")]
+    pub trait __FmtField<const N: usize> {
+        fn fmt_field(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+    }
+
+    /// Detects fields that also implement `DebugFields` through autoref specialization. Calling
+    /// `(&__Nested(field)).__nested_fields()` prefers `__NestedKind` when it's implemented and falls
+    /// back to `__LeafKind` otherwise
+    pub struct __Nested<'inner, T: ?Sized>(pub &'inner T);
+
+    pub trait __NestedKind<'inner> {
+        fn __nested_fields(&self) -> Option<&'inner dyn DynDebugFields>;
+    }
+
+    impl<'inner, T: DebugFields> __NestedKind<'inner> for __Nested<'inner, T> {
+        fn __nested_fields(&self) -> Option<&'inner dyn DynDebugFields> {
+            Some(self.0)
+        }
+    }
+
+    pub trait __LeafKind<'inner> {
+        fn __nested_fields(&self) -> Option<&'inner dyn DynDebugFields>;
+    }
+
+    impl<'inner, T: ?Sized> __LeafKind<'inner> for &__Nested<'inner, T> {
+        fn __nested_fields(&self) -> Option<&'inner dyn DynDebugFields> {
+            None
+        }
+    }
+
+    /// A field that's interpolated into a `Display` template. Skipped fields display as nothing.
+    /// Any of the `core::fmt` traits can be used e.g. `{field}`, `{field:?}` or `{field:x}`
+    pub struct __DisplayField<'inner, T: ?Sized> {
+        skipped: bool,
+        value: &'inner T,
+    }
+
+    impl<'inner, T: ?Sized> __DisplayField<'inner, T> {
+        pub fn new(skipped: bool, value: &'inner T) -> Self {
+            Self { skipped, value }
+        }
+    }
+
+    macro_rules! display_field_fmt {
+        ($( $fmt_trait:ident ),*) => {
+            $(
+                impl<T: fmt::$fmt_trait + ?Sized> fmt::$fmt_trait for __DisplayField<'_, T> {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        if self.skipped {
+                            Ok(())
+                        } else {
+                            fmt::$fmt_trait::fmt(self.value, f)
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    display_field_fmt!(
+        Display, Debug, LowerHex, UpperHex, Binary, Octal, LowerExp, UpperExp, Pointer
+    );
 }
@@ -0,0 +1,300 @@
+use core::fmt::{self, Write};
+
+/// A stand-in for `fmt::DebugStruct` that can end with a custom marker for the hidden fields
+///
+/// `fmt::DebugStruct` can only end with a bare `..`. Values that are pretty printed get formatted
+/// through a separate writer that carries over every formatting option aside from a custom fill
+/// character e.g. `{:->#8?}` pads with spaces instead
+pub struct DebugStruct<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+    has_fields: bool,
+    result: fmt::Result,
+}
+
+impl<'a, 'f> DebugStruct<'a, 'f> {
+    pub fn new(f: &'a mut fmt::Formatter<'f>, name: &str) -> Self {
+        let result = f.write_str(name);
+        Self {
+            f,
+            has_fields: false,
+            result,
+        }
+    }
+
+    pub fn field(&mut self, name: &str, value: &dyn fmt::Debug) -> &mut Self {
+        self.result = self.result.and_then(|()| {
+            if self.f.alternate() {
+                if !self.has_fields {
+                    self.f.write_str(" {\n")?;
+                }
+                let options = Options::of(self.f);
+                let mut writer = PadAdapter::new(self.f);
+                writer.write_str(name)?;
+                writer.write_str(": ")?;
+                options.write_alternate(&mut writer, value)?;
+                writer.write_str(",\n")
+            } else {
+                self.f
+                    .write_str(if self.has_fields { ", " } else { " { " })?;
+                self.f.write_str(name)?;
+                self.f.write_str(": ")?;
+                value.fmt(self.f)
+            }
+        });
+        self.has_fields = true;
+        self
+    }
+
+    pub fn finish(&mut self) -> fmt::Result {
+        self.result
+            .and_then(|()| match (self.has_fields, self.f.alternate()) {
+                (false, _) => Ok(()),
+                (true, true) => self.f.write_str("}"),
+                (true, false) => self.f.write_str(" }"),
+            })
+    }
+
+    pub fn finish_non_exhaustive(&mut self) -> fmt::Result {
+        self.finish_with_marker(&"..")
+    }
+
+    /// Ends with the `marker` as if it were the last field
+    pub fn finish_with_marker(&mut self, marker: &dyn fmt::Display) -> fmt::Result {
+        self.result.and_then(|()| {
+            if self.f.alternate() {
+                if !self.has_fields {
+                    self.f.write_str(" {\n")?;
+                }
+                writeln!(PadAdapter::new(self.f), "{marker}")?;
+                self.f.write_str("}")
+            } else {
+                self.f
+                    .write_str(if self.has_fields { ", " } else { " { " })?;
+                write!(self.f, "{marker} }}")
+            }
+        })
+    }
+}
+
+/// A stand-in for `fmt::DebugTuple` that can end with a marker for the hidden fields
+///
+/// `fmt::DebugTuple::finish_non_exhaustive()` is newer than the MSRV. This has the same caveat as
+/// [`DebugStruct`] with pretty printing
+pub struct DebugTuple<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+    has_fields: bool,
+    result: fmt::Result,
+}
+
+impl<'a, 'f> DebugTuple<'a, 'f> {
+    pub fn new(f: &'a mut fmt::Formatter<'f>, name: &str) -> Self {
+        let result = f.write_str(name);
+        Self {
+            f,
+            has_fields: false,
+            result,
+        }
+    }
+
+    pub fn field(&mut self, value: &dyn fmt::Debug) -> &mut Self {
+        self.result = self.result.and_then(|()| {
+            if self.f.alternate() {
+                if !self.has_fields {
+                    self.f.write_str("(\n")?;
+                }
+                let options = Options::of(self.f);
+                let mut writer = PadAdapter::new(self.f);
+                options.write_alternate(&mut writer, value)?;
+                writer.write_str(",\n")
+            } else {
+                self.f.write_str(if self.has_fields { ", " } else { "(" })?;
+                value.fmt(self.f)
+            }
+        });
+        self.has_fields = true;
+        self
+    }
+
+    pub fn finish(&mut self) -> fmt::Result {
+        self.result.and_then(|()| {
+            if self.has_fields {
+                self.f.write_str(")")
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    pub fn finish_non_exhaustive(&mut self) -> fmt::Result {
+        self.finish_with_marker(&"..")
+    }
+
+    /// Ends with the `marker` as if it were the last field
+    pub fn finish_with_marker(&mut self, marker: &dyn fmt::Display) -> fmt::Result {
+        self.result.and_then(|()| {
+            if self.f.alternate() {
+                if !self.has_fields {
+                    self.f.write_str("(\n")?;
+                }
+                writeln!(PadAdapter::new(self.f), "{marker}")?;
+            } else {
+                self.f.write_str(if self.has_fields { ", " } else { "(" })?;
+                write!(self.f, "{marker}")?;
+            }
+            self.f.write_str(")")
+        })
+    }
+}
+
+/// The formatting options that get carried over to values that are pretty printed through a
+/// [`PadAdapter`]
+///
+/// A `fmt::Formatter` can only be created by the formatting macros, so each combination of options
+/// gets its own format spec. The fill character is the only option that can't be expressed that
+/// way since it has to be a literal
+#[derive(Clone, Copy)]
+struct Options {
+    align: Option<fmt::Alignment>,
+    sign_plus: bool,
+    zero_pad: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+}
+
+impl Options {
+    fn of(f: &fmt::Formatter<'_>) -> Self {
+        Self {
+            align: f.align(),
+            sign_plus: f.sign_plus(),
+            zero_pad: f.sign_aware_zero_pad(),
+            width: f.width(),
+            precision: f.precision(),
+        }
+    }
+
+    /// Writes the `value` with `{:#?}` along with the rest of the options
+    fn write_alternate(self, w: &mut dyn Write, value: &dyn fmt::Debug) -> fmt::Result {
+        let Self {
+            align,
+            sign_plus,
+            zero_pad,
+            width,
+            precision,
+        } = self;
+
+        macro_rules! with_width {
+            ($flags:expr) => {
+                match (width, precision) {
+                    (None, None) => write!(w, concat!("{:", $flags, "?}"), value),
+                    (Some(width), None) => {
+                        write!(w, concat!("{:", $flags, "width$?}"), value, width = width)
+                    }
+                    (None, Some(precision)) => {
+                        write!(
+                            w,
+                            concat!("{:", $flags, ".precision$?}"),
+                            value,
+                            precision = precision
+                        )
+                    }
+                    (Some(width), Some(precision)) => {
+                        write!(
+                            w,
+                            concat!("{:", $flags, "width$.precision$?}"),
+                            value,
+                            width = width,
+                            precision = precision,
+                        )
+                    }
+                }
+            };
+        }
+        macro_rules! with_zero_pad {
+            ($flags:expr) => {
+                if zero_pad {
+                    with_width!(concat!($flags, "#0"))
+                } else {
+                    with_width!(concat!($flags, "#"))
+                }
+            };
+        }
+        macro_rules! with_sign {
+            ($flags:expr) => {
+                if sign_plus {
+                    with_zero_pad!(concat!($flags, "+"))
+                } else {
+                    with_zero_pad!($flags)
+                }
+            };
+        }
+
+        match align {
+            None => with_sign!(""),
+            Some(fmt::Alignment::Left) => with_sign!("<"),
+            Some(fmt::Alignment::Center) => with_sign!("^"),
+            Some(fmt::Alignment::Right) => with_sign!(">"),
+        }
+    }
+}
+
+/// Indents every line that's written through it
+struct PadAdapter<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+    on_newline: bool,
+}
+
+impl<'a, 'f> PadAdapter<'a, 'f> {
+    fn new(f: &'a mut fmt::Formatter<'f>) -> Self {
+        Self {
+            f,
+            on_newline: true,
+        }
+    }
+}
+
+impl Write for PadAdapter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for line in s.split_inclusive('\n') {
+            if self.on_newline {
+                self.f.write_str("    ")?;
+            }
+            self.on_newline = line.ends_with('\n');
+            self.f.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// `.. (2 hidden)` where each field tracks whether it was hidden
+pub struct HiddenCount<'a>(pub &'a [bool]);
+
+impl fmt::Display for HiddenCount<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.0.iter().filter(|skipped| **skipped).count();
+        write!(f, ".. ({count} hidden)")
+    }
+}
+
+/// `.. (skipped: cache, handle)` where `skipped` tracks which of the `names` were hidden
+pub struct HiddenNames<'a> {
+    pub names: &'a [&'a str],
+    pub skipped: &'a [bool],
+}
+
+impl fmt::Display for HiddenNames<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(".. (skipped: ")?;
+        let hidden = self
+            .names
+            .iter()
+            .zip(self.skipped)
+            .filter_map(|(name, skipped)| skipped.then_some(name));
+        for (i, name) in hidden.enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(name)?;
+        }
+        f.write_str(")")
+    }
+}
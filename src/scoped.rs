@@ -0,0 +1,25 @@
+use core::cell::Cell;
+use std::thread::LocalKey;
+
+/// Sets the thread-local `key` to `value` while `func` runs. The previous value gets restored
+/// afterwards even if `func` panics
+pub(crate) fn with_thread_local<T: Copy + 'static, R>(
+    key: &'static LocalKey<Cell<T>>,
+    value: T,
+    func: impl FnOnce() -> R,
+) -> R {
+    struct Restore<T: Copy + 'static> {
+        key: &'static LocalKey<Cell<T>>,
+        prev: T,
+    }
+
+    impl<T: Copy + 'static> Drop for Restore<T> {
+        fn drop(&mut self) {
+            self.key.with(|current| current.set(self.prev));
+        }
+    }
+
+    let prev = key.with(|current| current.replace(value));
+    let _restore = Restore { key, prev };
+    func()
+}
@@ -0,0 +1,182 @@
+use core::{fmt, time::Duration};
+
+const BYTE_UNITS: [&str; 7] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB"];
+
+/// Formats a byte count using binary units like `1.5 MiB`
+///
+/// This is what `#[debug(bytes_size)]` uses
+pub struct ByteSize<'inner, T: ?Sized>(pub &'inner T);
+
+impl<T: Integer + ?Sized> fmt::Debug for ByteSize<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0.to_i128();
+        if bytes.unsigned_abs() < 1_024 {
+            return write!(f, "{bytes} B");
+        }
+
+        let mut scaled = bytes as f64 / 1_024.0;
+        let mut units = BYTE_UNITS.iter();
+        let mut unit = units.next().unwrap();
+        // Anything that rounds up to `1024.0` is displayed as `1.0` of the next unit instead
+        while scaled.abs() >= 1_023.95 {
+            match units.next() {
+                Some(next) => unit = next,
+                None => break,
+            }
+            scaled /= 1_024.0;
+        }
+
+        write!(f, "{scaled:.1} {unit}")
+    }
+}
+
+/// Formats a [`Duration`] split into its components like `1m 30.2s`
+///
+/// This is what `#[debug(duration)]` uses
+pub struct HumanDuration<'inner>(pub &'inner Duration);
+
+impl fmt::Debug for HumanDuration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos();
+        if nanos < 1_000 {
+            return write!(f, "{nanos}ns");
+        }
+
+        // Everything else is displayed to a tenth of its unit, so it gets rounded before picking
+        // the unit or carrying over into minutes e.g. `999.96µs` is `1.0ms`
+        let tenths_of_micros = (nanos + 50) / 100;
+        if tenths_of_micros < 10_000 {
+            return write_tenths(f, tenths_of_micros, "µs");
+        }
+        let tenths_of_millis = (nanos + 50_000) / 100_000;
+        if tenths_of_millis < 10_000 {
+            return write_tenths(f, tenths_of_millis, "ms");
+        }
+
+        let tenths_of_secs = (nanos + 50_000_000) / 100_000_000;
+        let secs = tenths_of_secs / 10;
+        let days = secs / 86_400;
+        let hours = secs / 3_600 % 24;
+        let mins = secs / 60 % 60;
+
+        if days > 0 {
+            write!(f, "{days}d {hours}h {mins}m ")?;
+        } else if hours > 0 {
+            write!(f, "{hours}h {mins}m ")?;
+        } else if mins > 0 {
+            write!(f, "{mins}m ")?;
+        }
+        write_tenths(f, tenths_of_secs % 600, "s")
+    }
+}
+
+fn write_tenths(f: &mut fmt::Formatter<'_>, tenths: u128, unit: &str) -> fmt::Result {
+    write!(f, "{}.{}{unit}", tenths / 10, tenths % 10)
+}
+
+/// The unit that an integer timestamp is stored in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeUnit {
+    Secs,
+    Millis,
+}
+
+/// Formats an integer timestamp relative to the Unix epoch as an RFC 3339 UTC datetime like
+/// `2023-07-25T14:03:09Z`
+///
+/// This is what `#[debug(unix_time = secs)]` and `#[debug(unix_time = millis)]` use
+pub struct UnixTime<'inner, T: ?Sized> {
+    inner: &'inner T,
+    unit: TimeUnit,
+}
+
+impl<'inner, T: ?Sized> UnixTime<'inner, T> {
+    pub fn new(inner: &'inner T, unit: TimeUnit) -> Self {
+        Self { inner, unit }
+    }
+}
+
+impl<T: Integer + ?Sized> fmt::Debug for UnixTime<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timestamp = self.inner.to_i128();
+        let (secs, millis) = match self.unit {
+            TimeUnit::Secs => (timestamp, None),
+            TimeUnit::Millis => (
+                timestamp.div_euclid(1_000),
+                Some(timestamp.rem_euclid(1_000)),
+            ),
+        };
+
+        let days = secs.div_euclid(86_400);
+        let secs_of_day = secs.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        let (hour, min, sec) = (secs_of_day / 3_600, secs_of_day / 60 % 60, secs_of_day % 60);
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}"
+        )?;
+        if let Some(millis) = millis {
+            write!(f, ".{millis:03}")?;
+        }
+        f.write_str("Z")
+    }
+}
+
+// Converts days since the Unix epoch to a proleptic Gregorian `(year, month, day)`. This is
+// Howard Hinnant's `civil_from_days()` algorithm
+fn civil_from_days(days: i128) -> (i128, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u8;
+    let year = year_of_era + era * 400 + i128::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Primitive integers that can be used with [`ByteSize`] and [`UnixTime`]
+pub trait Integer {
+    fn to_i128(&self) -> i128;
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Integer for $ty {
+                fn to_i128(&self) -> i128 {
+                    i128::from(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl Integer for isize {
+    fn to_i128(&self) -> i128 {
+        *self as i128
+    }
+}
+
+impl Integer for usize {
+    fn to_i128(&self) -> i128 {
+        *self as i128
+    }
+}
+
+impl Integer for u128 {
+    fn to_i128(&self) -> i128 {
+        i128::try_from(*self).unwrap_or(i128::MAX)
+    }
+}
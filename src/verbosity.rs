@@ -0,0 +1,66 @@
+use core::{cell::Cell, fmt};
+use std::thread_local;
+
+thread_local! {
+    static VERBOSITY: Cell<u8> = const { Cell::new(0) };
+}
+
+/// Formats the value with fields up to the verbosity `level` displayed
+///
+/// Fields marked with `#[debug(level = N)]` are hidden unless they're formatted through this at a
+/// `level` of at least `N`. The verbosity applies to everything formatted within, so it also
+/// carries over to any nested `SmartDebug` fields
+///
+/// The verbosity is kept in a thread-local, so this only exists with the `std` feature. Without it
+/// fields marked with `#[debug(level = N)]` are always hidden
+///
+/// ```
+/// use smart_debug::SmartDebug;
+///
+/// #[derive(SmartDebug)]
+/// struct Conn {
+///     peer: &'static str,
+///     #[debug(level = 2)]
+///     retries: u8,
+/// }
+///
+/// let conn = Conn { peer: "10.0.0.1", retries: 3 };
+/// assert_eq!(format!("{conn:?}"), r#"Conn { peer: "10.0.0.1", .. }"#);
+/// assert_eq!(
+///     format!("{:?}", smart_debug::verbose(&conn, 2)),
+///     r#"Conn { peer: "10.0.0.1", retries: 3 }"#,
+/// );
+/// ```
+pub fn verbose<T: ?Sized>(value: &T, level: u8) -> Verbose<'_, T> {
+    Verbose {
+        inner: value,
+        level,
+    }
+}
+
+/// The adapter returned from [`verbose()`]
+pub struct Verbose<'inner, T: ?Sized> {
+    inner: &'inner T,
+    level: u8,
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Verbose<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        with_verbosity(self.level, || self.inner.fmt(f))
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for Verbose<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        with_verbosity(self.level, || self.inner.fmt(f))
+    }
+}
+
+fn with_verbosity<R>(level: u8, func: impl FnOnce() -> R) -> R {
+    crate::scoped::with_thread_local(&VERBOSITY, level, func)
+}
+
+/// The verbosity that's currently being formatted at
+pub(crate) fn current() -> u8 {
+    VERBOSITY.with(Cell::get)
+}
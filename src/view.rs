@@ -0,0 +1,98 @@
+use core::{any::TypeId, fmt, marker::PhantomData};
+#[cfg(feature = "std")]
+use {core::cell::Cell, std::thread_local};
+
+#[cfg(feature = "std")]
+thread_local! {
+    static CURRENT: Cell<Option<TypeId>> = const { Cell::new(None) };
+}
+
+/// Formats a type with the rules of its named view `V`
+///
+/// This is implemented by the `SmartDebug` derive for each view that's used in a
+/// `#[debug(view(V, ...))]` attribute where `V` is any `'static` type that names the view. Views
+/// should be formatted through [`DebugViewExt::debug_view()`] which also makes the view carry
+/// over to any nested `SmartDebug` types that have the same view when the `std` feature is enabled
+pub trait DebugView<V: ?Sized + 'static> {
+    fn fmt_view(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// Adds [`DebugViewExt::debug_view()`] to everything
+pub trait DebugViewExt {
+    /// Formats the value with the rules of its named view `V`
+    ///
+    /// Fields can have separate attributes for each view with `#[debug(view(V, ...))]` which take
+    /// precedence over the regular attributes of the same kind
+    ///
+    /// The view carries over to nested `SmartDebug` values through a thread-local, so without the
+    /// `std` feature it only applies to the value itself and anything nested within is formatted
+    /// with its regular attributes
+    ///
+    /// ```
+    /// use smart_debug::{DebugViewExt, SmartDebug};
+    ///
+    /// enum Audit {}
+    ///
+    /// #[derive(SmartDebug)]
+    /// struct Login {
+    ///     user: &'static str,
+    ///     #[debug(view(Audit, skip))]
+    ///     attempts: u8,
+    ///     #[debug(skip, view(Audit, no_skip))]
+    ///     source_ip: &'static str,
+    /// }
+    ///
+    /// let login = Login { user: "harper", attempts: 2, source_ip: "10.0.0.1" };
+    /// assert_eq!(format!("{login:?}"), r#"Login { user: "harper", attempts: 2, .. }"#);
+    /// assert_eq!(
+    ///     format!("{:?}", login.debug_view::<Audit>()),
+    ///     r#"Login { user: "harper", source_ip: "10.0.0.1", .. }"#,
+    /// );
+    /// ```
+    fn debug_view<V: ?Sized + 'static>(&self) -> View<'_, V, Self>
+    where
+        Self: DebugView<V>,
+    {
+        View {
+            inner: self,
+            view: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> DebugViewExt for T {}
+
+/// The adapter returned from [`DebugViewExt::debug_view()`]
+pub struct View<'inner, V: ?Sized, T: ?Sized> {
+    inner: &'inner T,
+    view: PhantomData<fn() -> *const V>,
+}
+
+impl<V: ?Sized + 'static, T: DebugView<V> + ?Sized> fmt::Debug for View<'_, V, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        with_view(TypeId::of::<V>(), || self.inner.fmt_view(f))
+    }
+}
+
+#[cfg(feature = "std")]
+fn with_view<R>(view: TypeId, func: impl FnOnce() -> R) -> R {
+    crate::scoped::with_thread_local(&CURRENT, Some(view), func)
+}
+
+// Nothing to carry the view over to nested types without thread-locals
+#[cfg(not(feature = "std"))]
+fn with_view<R>(_view: TypeId, func: impl FnOnce() -> R) -> R {
+    func()
+}
+
+/// Whether the view `V` is the one that's currently being formatted
+#[cfg(feature = "std")]
+pub(crate) fn is_current<V: ?Sized + 'static>() -> bool {
+    CURRENT.with(Cell::get) == Some(TypeId::of::<V>())
+}
+
+#[cfg(not(feature = "std"))]
+#[allow(clippy::extra_unused_type_parameters)]
+pub(crate) fn is_current<V: ?Sized + 'static>() -> bool {
+    false
+}
@@ -0,0 +1,15 @@
+/// Derives every item with both `SmartDebug` and `#[derive(Debug)]` in the `smart` and
+/// `std_derive` modules respectively
+macro_rules! both {
+    ($( $item:item )*) => {
+        mod smart {
+            use smart_debug::SmartDebug;
+            $( #[derive(SmartDebug)] $item )*
+        }
+        // Fields only read by the derived `Debug` impl count as dead code
+        #[allow(dead_code)]
+        mod std_derive {
+            $( #[derive(Debug)] $item )*
+        }
+    };
+}
@@ -0,0 +1,2 @@
+//! Everything lives in the integration tests. Only `facade` is a dependency, so the generated code
+//! has to go through `#[debug(crate = facade::smart_debug)]` to find anything
@@ -0,0 +1,47 @@
+use facade::smart_debug::{SmartDebug, SmartDisplay};
+
+#[test]
+fn debug() {
+    #[derive(SmartDebug)]
+    #[debug(crate = facade::smart_debug, exhaustive_marker = count)]
+    struct Packet {
+        #[debug(hex)]
+        payload: Vec<u8>,
+        #[debug(level = 1)]
+        checksum: u32,
+        #[debug(skip_if = 0)]
+        retries: u8,
+    }
+
+    #[derive(SmartDebug)]
+    #[debug(crate = facade::smart_debug, tuple_skip = omit)]
+    struct Pair(u8, #[debug(skip)] u8);
+
+    let packet = Packet {
+        payload: vec![0xde, 0xad],
+        checksum: 42,
+        retries: 0,
+    };
+    assert_eq!(
+        format!("{packet:?}"),
+        "Packet { payload: 0xdead, .. (2 hidden) }"
+    );
+    assert_eq!(format!("{:?}", Pair(1, 2)), "Pair(1, ..)");
+}
+
+#[test]
+fn display() {
+    #[derive(SmartDisplay)]
+    #[display(crate = facade::smart_debug, "{user}@{host}")]
+    struct Address {
+        user: &'static str,
+        #[display(skip_if = "")]
+        host: &'static str,
+    }
+
+    let address = Address {
+        user: "root",
+        host: "example.org",
+    };
+    assert_eq!(address.to_string(), "root@example.org");
+}
@@ -0,0 +1,3 @@
+//! A crate that re-exports `smart_debug` for crates that don't depend on it directly
+
+pub use smart_debug;
@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use std::{cell::RefCell, rc::Rc};
+
+use smart_debug::SmartDebug;
+
+#[test]
+fn rc_graph() {
+    #[derive(SmartDebug)]
+    #[debug(cycle_safe)]
+    struct Node {
+        name: &'static str,
+        edges: Vec<Rc<RefCell<Node>>>,
+    }
+
+    let a = Rc::new(RefCell::new(Node {
+        name: "a",
+        edges: Vec::new(),
+    }));
+    let b = Rc::new(RefCell::new(Node {
+        name: "b",
+        edges: vec![Rc::clone(&a)],
+    }));
+    a.borrow_mut().edges.push(Rc::clone(&b));
+
+    let a_addr = format!("{:p}", &*a.borrow());
+    let expected = format!(
+        "Node {{ name: \"a\", edges: [RefCell {{ value: Node {{ name: \"b\", edges: [RefCell {{ \
+         value: <cycle: Node@{a_addr}> }}] }} }}] }}"
+    );
+    assert_eq!(format!("{:?}", a.borrow()), expected);
+    // Nothing is left over from the last time it was formatted
+    assert_eq!(format!("{:?}", a.borrow()), expected);
+
+    // Values that are shared without being part of a cycle are displayed in full each time
+    let shared = Rc::new(RefCell::new(Node {
+        name: "shared",
+        edges: Vec::new(),
+    }));
+    let diamond = Node {
+        name: "diamond",
+        edges: vec![Rc::clone(&shared), shared],
+    };
+    insta::assert_snapshot!(format!("{diamond:#?}"));
+
+    // Break the cycle, so that the nodes get freed
+    a.borrow_mut().edges.clear();
+}
@@ -0,0 +1,51 @@
+//! `defmt` can't actually log anything without a global logger, so this only checks that the
+//! generated impls compile
+#![cfg(feature = "defmt")]
+
+use smart_debug::SmartDefmt;
+
+fn assert_format<T: defmt::Format>() {}
+
+#[test]
+fn named() {
+    #[derive(SmartDefmt, Default, PartialEq)]
+    #[debug(skip_defaults)]
+    struct Named {
+        #[debug(no_skip)]
+        displayed: u8,
+        #[debug(skip)]
+        skipped: u16,
+        #[debug(skip_if = false)]
+        skip_if: bool,
+        #[debug("<redacted>")]
+        password: &'static str,
+        #[debug("{:#x}")]
+        hex: u32,
+        #[debug(bytes_size)]
+        size: u64,
+    }
+
+    assert_format::<Named>();
+}
+
+#[test]
+fn tuple() {
+    #[derive(SmartDefmt)]
+    #[allow(dead_code)]
+    struct Tuple(u8, #[debug(skip)] (), #[debug("<redacted>")] &'static str);
+
+    assert_format::<Tuple>();
+}
+
+#[test]
+fn unit_and_bare() {
+    #[derive(SmartDefmt)]
+    struct Unit;
+
+    #[derive(SmartDefmt)]
+    #[debug("{ custom }")]
+    struct Bare;
+
+    assert_format::<Unit>();
+    assert_format::<Bare>();
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "json")]
+
+use serde::Serialize;
+use smart_debug::SmartDebug;
+
+#[derive(Serialize, SmartDebug, Default)]
+#[debug(skip_defaults)]
+struct Request {
+    method: &'static str,
+    path: &'static str,
+    retries: u8,
+    #[debug(skip)]
+    body: Vec<u8>,
+    #[debug(rename = "auth")]
+    credentials: Credentials,
+    #[debug(hex)]
+    checksum: [u8; 2],
+}
+
+#[derive(Serialize, SmartDebug, Default, PartialEq)]
+struct Credentials {
+    user: &'static str,
+    #[debug("<redacted>")]
+    token: &'static str,
+}
+
+#[derive(Serialize, SmartDebug)]
+struct Tuple(u8, #[debug(skip)] u8, &'static str);
+
+fn request() -> Request {
+    Request {
+        method: "GET",
+        path: "/search?q=\"rust\"",
+        body: b"ignored".to_vec(),
+        credentials: Credentials {
+            user: "ferris",
+            token: "hunter2",
+        },
+        checksum: [0xab, 0xcd],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn compact() {
+    let request = request();
+
+    insta::with_settings!({ info => &request }, {
+        insta::assert_snapshot!(smart_debug::json(&request).to_string());
+    });
+}
+
+#[test]
+fn pretty() {
+    let request = request();
+
+    insta::with_settings!({ info => &request }, {
+        insta::assert_snapshot!(format!("{:#}", smart_debug::json(&request)));
+    });
+}
+
+#[test]
+fn tuple() {
+    let tuple = Tuple(1, 2, "tab\there");
+
+    insta::with_settings!({ info => &tuple }, {
+        insta::assert_snapshot!(smart_debug::json(&tuple).to_string());
+    });
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "log-kv")]
+
+use log::kv::{self, Key, Source, Value, VisitSource};
+use serde::Serialize;
+use smart_debug::SmartLogKv;
+
+/// Records every visited pair as a `key: value` line
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl<'kvs> VisitSource<'kvs> for Recorder {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.push(format!("{key}: {value}"));
+        Ok(())
+    }
+}
+
+fn record(source: &impl Source) -> Vec<String> {
+    let mut recorder = Recorder::default();
+    source.visit(&mut recorder).unwrap();
+    recorder.0
+}
+
+#[test]
+fn named() {
+    #[derive(Serialize, SmartLogKv, Default)]
+    #[debug(skip_defaults)]
+    struct Request {
+        method: &'static str,
+        path: &'static str,
+        retries: u8,
+        #[debug(skip)]
+        body: Vec<u8>,
+        #[debug("<redacted>")]
+        token: &'static str,
+        #[debug(rename = "elapsed", duration)]
+        duration: std::time::Duration,
+    }
+
+    let request = Request {
+        method: "GET",
+        path: "/index.html",
+        body: b"ignored".to_vec(),
+        token: "hunter2",
+        duration: std::time::Duration::from_millis(1_500),
+        ..Default::default()
+    };
+
+    insta::with_settings!({ info => &request }, {
+        insta::assert_debug_snapshot!(record(&request));
+    });
+}
+
+#[test]
+fn tuple() {
+    #[derive(Serialize, SmartLogKv)]
+    struct Tuple(u8, #[debug(skip)] u8, #[debug(hex)] [u8; 2]);
+
+    let tuple = Tuple(1, 2, [0xab, 0xcd]);
+
+    insta::with_settings!({ info => &tuple }, {
+        insta::assert_debug_snapshot!(record(&tuple));
+    });
+}
@@ -0,0 +1,46 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::format;
+use smart_debug::SmartDebug;
+
+#[derive(SmartDebug, Default)]
+#[debug(skip_defaults)]
+struct Sensor {
+    id: u8,
+    #[debug(hex)]
+    status: [u8; 2],
+    #[debug("<calibration>")]
+    calibration: [i16; 3],
+    // Only exists to be skipped
+    #[allow(dead_code)]
+    #[debug(skip)]
+    scratch: [u8; 4],
+}
+
+// Cycles are only detected with `std`, but the attr still has to work without it
+#[derive(SmartDebug)]
+#[debug(cycle_safe)]
+struct Reading {
+    value: i32,
+}
+
+#[test]
+fn derive_without_std() {
+    let sensor = Sensor {
+        id: 3,
+        status: [0xbe, 0xef],
+        calibration: [1, -2, 3],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        format!("{sensor:?}"),
+        "Sensor { id: 3, status: 0xbeef, calibration: <calibration>, .. }",
+    );
+    assert_eq!(
+        format!("{:?}", Reading { value: -1 }),
+        "Reading { value: -1 }"
+    );
+}
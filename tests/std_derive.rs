@@ -0,0 +1,155 @@
+//! `SmartDebug` without any attrs should be indistinguishable from `#[derive(Debug)]`, so every
+//! shape here gets derived both ways and the output is compared for all sorts of values
+
+use proptest::prelude::*;
+
+#[macro_use]
+mod common;
+
+both! {
+    pub struct Named {
+        pub id: u32,
+        pub name: String,
+        pub tags: Vec<Option<i8>>,
+    }
+
+    pub struct Tuple(pub i64, pub f64, pub String);
+
+    pub struct Unit;
+
+    pub struct EmptyNamed {}
+
+    pub struct EmptyTuple();
+
+    pub struct Generic<T, U> {
+        pub first: T,
+        pub rest: Vec<U>,
+    }
+
+    pub struct Bounded<T>
+    where
+        T: Clone,
+    {
+        pub value: T,
+    }
+
+    pub struct Lifetimes<'a, 'b> {
+        pub text: &'a str,
+        pub bytes: &'b [u8],
+    }
+
+    pub struct ConstGeneric<const N: usize> {
+        pub items: [u16; N],
+    }
+
+    pub struct r#Loop {
+        pub r#type: u8,
+        pub r#match: bool,
+        pub r#fn: char,
+    }
+
+    pub struct ZeroSized {
+        pub unit: (),
+        pub empty: [u8; 0],
+        pub unit_struct: Unit,
+        pub empty_tuple: EmptyTuple,
+    }
+
+    pub struct Nested {
+        pub named: Named,
+        pub tuple: Tuple,
+        pub pair: (Option<Unit>, EmptyNamed),
+    }
+
+    pub struct Tail<T: ?Sized> {
+        pub len: usize,
+        pub data: T,
+    }
+}
+
+/// Builds the same value with both derives and compares their compact and pretty output
+macro_rules! assert_same {
+    ($value:expr) => {{
+        let smart = {
+            use smart::*;
+            $value
+        };
+        let std = {
+            use std_derive::*;
+            $value
+        };
+        prop_assert_eq!(format!("{smart:?}"), format!("{std:?}"));
+        prop_assert_eq!(format!("{smart:#?}"), format!("{std:#?}"));
+    }};
+}
+
+proptest! {
+    #[test]
+    fn named(id: u32, name: String, tags: Vec<Option<i8>>) {
+        assert_same!(Named { id, name: name.clone(), tags: tags.clone() });
+    }
+
+    #[test]
+    fn tuple(int: i64, float: f64, string: String) {
+        assert_same!(Tuple(int, float, string.clone()));
+    }
+
+    #[test]
+    fn generic(first: String, rest: Vec<(u8, bool)>) {
+        assert_same!(Generic { first: first.clone(), rest: rest.clone() });
+    }
+
+    #[test]
+    fn bounded(value: Option<u64>) {
+        assert_same!(Bounded { value });
+    }
+
+    #[test]
+    fn lifetimes(text: String, bytes: Vec<u8>) {
+        assert_same!(Lifetimes { text: &text, bytes: &bytes });
+    }
+
+    #[test]
+    fn const_generic(items: [u16; 5]) {
+        assert_same!(ConstGeneric { items });
+    }
+
+    #[test]
+    fn raw_idents(r#type: u8, r#match: bool, r#fn: char) {
+        assert_same!(Loop { r#type, r#match, r#fn });
+    }
+
+    #[test]
+    fn nested(id: u32, name: String, int: i64, float: f64, is_some: bool) {
+        assert_same!(Nested {
+            named: Named { id, name: name.clone(), tags: vec![Some(1), None] },
+            tuple: Tuple(int, float, name.clone()),
+            pair: (is_some.then_some(Unit), EmptyNamed {}),
+        });
+    }
+
+    #[test]
+    fn unsized_tail(data: [u8; 3]) {
+        let smart: &smart::Tail<[u8]> = &smart::Tail { len: 3, data };
+        let std: &std_derive::Tail<[u8]> = &std_derive::Tail { len: 3, data };
+        prop_assert_eq!(format!("{smart:?}"), format!("{std:?}"));
+        prop_assert_eq!(format!("{smart:#?}"), format!("{std:#?}"));
+    }
+}
+
+#[test]
+fn fieldless() {
+    let check = || -> Result<(), TestCaseError> {
+        assert_same!(Unit);
+        assert_same!(EmptyNamed {});
+        assert_same!(EmptyTuple());
+        assert_same!(ZeroSized {
+            unit: (),
+            empty: [],
+            unit_struct: Unit,
+            empty_tuple: EmptyTuple(),
+        });
+        Ok(())
+    };
+    check().unwrap();
+}
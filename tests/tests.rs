@@ -1,5 +1,8 @@
+// Some of the types here only exist to be formatted, so their fields are never read
+#![allow(dead_code)]
+
 use serde::Serialize;
-use smart_debug::SmartDebug;
+use smart_debug::{SmartDebug, SmartDisplay};
 
 #[test]
 fn basic() {
@@ -135,6 +138,63 @@ fn tuple_struct() {
     });
 }
 
+#[test]
+fn tuple_skipping() {
+    #[derive(Serialize, SmartDebug, Default)]
+    #[debug(skip_defaults, placeholder = "<default>")]
+    struct Placeholder(u8, u8, u8, u8);
+
+    #[derive(Serialize, SmartDebug, Default)]
+    #[debug(skip_defaults, tuple_skip = omit)]
+    struct Omit(u8, u8, u8, u8);
+
+    #[derive(Serialize, SmartDebug, Default)]
+    #[debug(skip_defaults, tuple_skip = omit, exhaustive_marker = count)]
+    struct OmitCount(u8, u8, u8, u8);
+
+    #[derive(Serialize, SmartDebug, Default)]
+    #[debug(skip_defaults, tuple_skip = trailing)]
+    struct Trailing(u8, u8, u8, u8);
+
+    #[derive(Serialize, SmartDebug)]
+    #[non_exhaustive]
+    struct NonExhaustive(u8);
+
+    let placeholder = Placeholder(0, 0, 0, 255);
+    let omit = Omit(0, 0, 0, 255);
+    let omit_count = OmitCount(0, 0, 0, 255);
+    let trailing = Trailing(0, 128, 0, 0);
+    let non_exhaustive = NonExhaustive(1);
+
+    insta::with_settings!({
+        info => &(&placeholder, &omit, &omit_count, &trailing, &non_exhaustive),
+    }, {
+        insta::assert_snapshot!(format!(
+            "{placeholder:?}\n{omit:?}\n{omit_count:?}\n{trailing:?}\n{non_exhaustive:?}"
+        ));
+        insta::assert_snapshot!(format!("{omit:#?}\n{trailing:#?}"));
+    });
+}
+
+#[test]
+fn indexed_tuple_struct() {
+    #[derive(Serialize, SmartDebug)]
+    #[debug(indexed)]
+    struct Row(
+        u32,
+        #[debug(rename = "name")] &'static str,
+        #[debug(skip)] f64,
+        bool,
+    );
+
+    let row = Row(7, "ferris", 0.5, true);
+
+    insta::with_settings!({ info => &row }, {
+        insta::assert_snapshot!(format!("{row:?}"));
+        insta::assert_debug_snapshot!(row);
+    });
+}
+
 #[test]
 fn container_lit_str() {
     #[derive(Serialize, SmartDebug)]
@@ -147,3 +207,800 @@ fn container_lit_str() {
         insta::assert_debug_snapshot!(custom);
     });
 }
+
+#[test]
+fn item_limits() {
+    use std::collections::{BTreeMap, VecDeque};
+
+    #[derive(Serialize, SmartDebug)]
+    struct ItemLimits {
+        #[debug(head = 3, tail = 2)]
+        head_and_tail: Vec<u32>,
+        #[debug(max_items = 2)]
+        max_items: BTreeMap<&'static str, u8>,
+        #[debug(tail = 1)]
+        just_tail: [char; 3],
+        #[debug(max_items = 10)]
+        fits: VecDeque<()>,
+    }
+
+    let item_limits = ItemLimits {
+        head_and_tail: (1..=10).collect(),
+        max_items: [("a", 1), ("b", 2), ("c", 3)].into_iter().collect(),
+        just_tail: ['x', 'y', 'z'],
+        fits: VecDeque::from([(), ()]),
+    };
+
+    insta::with_settings!({ info => &item_limits }, {
+        insta::assert_snapshot!(format!("{item_limits:?}"));
+        insta::assert_debug_snapshot!(item_limits);
+    });
+
+    // Borrowed and boxed collections work just like the ones they point to
+    #[derive(SmartDebug)]
+    struct Buffers {
+        #[debug(max_items = 2)]
+        borrowed: &'static [u32],
+        #[debug(max_items = 2)]
+        boxed: Box<[u32]>,
+    }
+
+    let buffers = Buffers {
+        borrowed: &[1, 2, 3],
+        boxed: Box::new([4, 5, 6]),
+    };
+    assert_eq!(
+        format!("{buffers:?}"),
+        "Buffers { borrowed: [1, 2, ...] (len = 3), boxed: [4, 5, ...] (len = 3) }",
+    );
+}
+
+#[test]
+fn byte_buffers() {
+    #[derive(Serialize, SmartDebug)]
+    struct ByteBuffers {
+        #[debug(hex)]
+        hex: Vec<u8>,
+        #[debug(bytes)]
+        bytes: [u8; 9],
+        #[debug(hex)]
+        empty: &'static [u8],
+    }
+
+    let byte_buffers = ByteBuffers {
+        hex: b"Hello, World!\n\x00\x01\x02\xff".to_vec(),
+        bytes: *b"\"Hi\"\t\x00\\\x7f ",
+        empty: b"",
+    };
+
+    insta::with_settings!({ info => &byte_buffers }, {
+        insta::assert_snapshot!(format!("{byte_buffers:?}"));
+        insta::assert_debug_snapshot!(byte_buffers);
+    });
+
+    assert_eq!(format!("{:?}", smart_debug::Hex(&[])), "[]");
+    assert_eq!(format!("{:#?}", smart_debug::Hex(&[])), "[]");
+}
+
+#[test]
+fn human_units() {
+    use std::time::Duration;
+
+    #[derive(Serialize, SmartDebug)]
+    struct HumanUnits {
+        #[debug(bytes_size)]
+        small: u16,
+        #[debug(bytes_size)]
+        size: u64,
+        #[debug(duration)]
+        short: Duration,
+        #[debug(duration)]
+        long: Duration,
+        #[debug(unix_time = secs)]
+        created: i64,
+        #[debug(unix_time = millis)]
+        updated: u64,
+        #[debug(unix_time = secs)]
+        before_epoch: i32,
+    }
+
+    let human_units = HumanUnits {
+        small: 512,
+        size: 1_572_864,
+        short: Duration::from_micros(2_500),
+        long: Duration::from_millis(90_200),
+        created: 1_690_293_789,
+        updated: 1_709_251_199_123,
+        before_epoch: -1,
+    };
+
+    insta::with_settings!({ info => &human_units }, {
+        insta::assert_debug_snapshot!(human_units);
+    });
+
+    // Values get rounded before picking the unit
+    let byte_size = |bytes: u64| format!("{:?}", smart_debug::ByteSize(&bytes));
+    assert_eq!(byte_size(1_023), "1023 B");
+    assert_eq!(byte_size(1_024), "1.0 KiB");
+    assert_eq!(byte_size(1_048_524), "1023.9 KiB");
+    assert_eq!(byte_size(1_048_575), "1.0 MiB");
+    assert_eq!(byte_size(1_073_741_823), "1.0 GiB");
+
+    let duration = |nanos: u64| {
+        format!(
+            "{:?}",
+            smart_debug::HumanDuration(&Duration::from_nanos(nanos))
+        )
+    };
+    assert_eq!(duration(999), "999ns");
+    assert_eq!(duration(999_949), "999.9µs");
+    assert_eq!(duration(999_960), "1.0ms");
+    assert_eq!(duration(999_960_000), "1.0s");
+    assert_eq!(duration(59_960_000_000), "1m 0.0s");
+    assert_eq!(duration(119_960_000_000), "2m 0.0s");
+    assert_eq!(duration(3_599_960_000_000), "1h 0m 0.0s");
+    assert_eq!(duration(86_399_960_000_000), "1d 0h 0m 0.0s");
+}
+
+#[test]
+fn inline() {
+    #[derive(Serialize, SmartDebug)]
+    struct InlineField {
+        #[debug(inline)]
+        inline: Option<(u8, u8)>,
+        not_inline: Option<(u8, u8)>,
+    }
+
+    let inline_field = InlineField {
+        inline: Some((1, 2)),
+        not_inline: Some((3, 4)),
+    };
+
+    insta::with_settings!({ info => &inline_field }, {
+        insta::assert_debug_snapshot!(inline_field);
+    });
+
+    #[derive(Serialize, SmartDebug)]
+    #[debug(inline_fields)]
+    struct InlineFields {
+        #[debug(head = 1, tail = 1)]
+        items: Vec<u8>,
+        pair: (bool, bool),
+    }
+
+    let inline_fields = InlineFields {
+        items: vec![1, 2, 3],
+        pair: (true, false),
+    };
+
+    insta::with_settings!({ info => &inline_fields }, {
+        insta::assert_debug_snapshot!(inline_fields);
+    });
+}
+
+#[test]
+fn fmt_traits() {
+    #[derive(Serialize, SmartDebug)]
+    struct FmtTraits {
+        #[debug(display)]
+        display: &'static str,
+        #[debug(lower_hex)]
+        lower_hex: u32,
+        #[debug(upper_hex)]
+        upper_hex: u32,
+        #[debug(binary)]
+        binary: u8,
+        #[debug(octal)]
+        octal: u16,
+        #[debug(lower_exp)]
+        lower_exp: f64,
+        #[debug(upper_exp)]
+        upper_exp: f64,
+    }
+
+    let fmt_traits = FmtTraits {
+        display: "No \"escaping\" here",
+        lower_hex: 0xdead_beef,
+        upper_hex: 0xdead_beef,
+        binary: 0b1010,
+        octal: 0o755,
+        lower_exp: 1234.5,
+        upper_exp: 0.00012,
+    };
+
+    insta::with_settings!({ info => &fmt_traits }, {
+        insta::assert_snapshot!(format!("{fmt_traits:?}"));
+    });
+
+    // The formatter gets forwarded, so things like width and precision are respected
+    #[derive(SmartDebug)]
+    struct Padded(#[debug(display)] f64, #[debug(lower_hex)] u8);
+
+    assert_eq!(
+        format!("{:>6.2?}", Padded(1.23456, 10)),
+        "Padded(  1.23,      a)"
+    );
+}
+
+#[test]
+fn smart_display() {
+    #[derive(Serialize, SmartDisplay)]
+    #[display("{user}@{host}{port}")]
+    struct Address {
+        user: &'static str,
+        host: &'static str,
+        #[display(skip_if = 22, ":{}")]
+        port: u16,
+    }
+
+    let default_port = Address {
+        user: "root",
+        host: "example.org",
+        port: 22,
+    };
+    let custom_port = Address {
+        port: 2222,
+        ..default_port
+    };
+
+    insta::with_settings!({ info => &custom_port }, {
+        insta::assert_snapshot!(format!("{default_port} | {custom_port}"));
+    });
+
+    #[derive(Serialize, SmartDisplay)]
+    #[display("#{0:02} {1:>8}")]
+    struct Tuple(u8, #[display(lower_hex)] u32);
+
+    let tuple = Tuple(7, 0xbeef);
+
+    insta::with_settings!({ info => &tuple }, {
+        insta::assert_snapshot!(tuple.to_string());
+    });
+
+    // Fields can be formatted with `Debug` inside a template too
+    #[derive(Serialize, SmartDisplay)]
+    #[display("{name:?} = {value:?}")]
+    struct Quoted {
+        name: &'static str,
+        #[display(skip_if = None)]
+        value: Option<u8>,
+    }
+
+    assert_eq!(
+        Quoted {
+            name: "a",
+            value: Some(1)
+        }
+        .to_string(),
+        r#""a" = Some(1)"#
+    );
+    assert_eq!(
+        Quoted {
+            name: "a",
+            value: None
+        }
+        .to_string(),
+        r#""a" = "#
+    );
+
+    // Without a template fields are displayed like `Debug` would, just using `Display` for each one
+    #[derive(Serialize, SmartDisplay, Default)]
+    #[display(skip_defaults)]
+    struct NoTemplate {
+        text: &'static str,
+        #[display(no_skip)]
+        count: u32,
+        #[display("<redacted>")]
+        secret: &'static str,
+    }
+
+    let no_template = NoTemplate {
+        text: "Not \"escaped\"",
+        ..Default::default()
+    };
+
+    insta::with_settings!({ info => &no_template }, {
+        insta::assert_snapshot!(no_template.to_string());
+    });
+}
+
+#[test]
+fn generic_bounds() {
+    use std::marker::PhantomData;
+
+    struct NotDebug;
+
+    // Only type params of fields that get formatted as is need to implement the trait
+    #[derive(SmartDebug)]
+    struct Opaque<T> {
+        id: u64,
+        #[debug(skip)]
+        inner: T,
+    }
+
+    // Fields with a custom representation don't need to be `Debug` either
+    #[derive(SmartDebug)]
+    struct Wrapped<T: AsRef<[u8]>> {
+        #[debug(wrapper = ByteLen)]
+        data: T,
+    }
+
+    struct ByteLen<'a, T>(&'a T);
+
+    impl<T: AsRef<[u8]>> std::fmt::Debug for ByteLen<'_, T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} bytes", self.0.as_ref().len())
+        }
+    }
+
+    impl AsRef<[u8]> for NotDebug {
+        fn as_ref(&self) -> &[u8] {
+            b"abc"
+        }
+    }
+
+    #[derive(SmartDisplay)]
+    #[display("#{id}")]
+    struct Id<T> {
+        id: u64,
+        marker: PhantomData<T>,
+    }
+
+    #[derive(Debug)]
+    struct OnlyDebug;
+
+    // Template args are bounded by whichever trait their spec formats them with
+    #[derive(SmartDisplay)]
+    #[display("{0:?} @ {1:#x}")]
+    struct Tagged<T, U>(T, U);
+
+    let opaque = Opaque {
+        id: 1,
+        inner: NotDebug,
+    };
+    let id = Id::<NotDebug> {
+        id: 2,
+        marker: PhantomData,
+    };
+    assert_eq!(format!("{opaque:?}"), "Opaque { id: 1, .. }");
+    assert_eq!(
+        format!("{:?}", Wrapped { data: NotDebug }),
+        "Wrapped { data: 3 bytes }",
+    );
+    assert_eq!(id.to_string(), "#2");
+    assert_eq!(Tagged(OnlyDebug, 255u8).to_string(), "OnlyDebug @ 0xff");
+}
+
+#[test]
+fn rename() {
+    #[derive(Serialize, SmartDebug)]
+    struct Rename {
+        #[debug(rename = "type")]
+        kind: &'static str,
+        #[debug(rename = "id", "#{}")]
+        identifier: u32,
+    }
+
+    let rename = Rename {
+        kind: "user",
+        identifier: 42,
+    };
+
+    insta::with_settings!({ info => &rename }, {
+        insta::assert_debug_snapshot!(rename);
+    });
+}
+
+#[test]
+fn alt_reprs() {
+    #[derive(Serialize, SmartDebug)]
+    struct Blob {
+        #[debug("{:.4}…", alt = "{}")]
+        hash: String,
+        #[debug(max_items = 2, alt_wrapper = smart_debug::ByteStr)]
+        payload: Vec<u8>,
+        #[debug(alt = "<{} lines>")]
+        lines: usize,
+    }
+
+    let blob = Blob {
+        hash: "3fa9c2e1b8d74f60a5e3c9b2d1f0e8a7c6b5d4e3f2a1b0c9d8e7f6a5b4c3d2e1".to_owned(),
+        payload: b"hello".to_vec(),
+        lines: 3,
+    };
+
+    insta::with_settings!({ info => &blob }, {
+        insta::assert_snapshot!(format!("{blob:?}"));
+        insta::assert_debug_snapshot!(blob);
+    });
+
+    // Inlining keeps the alt repr around
+    #[derive(SmartDebug)]
+    #[debug(inline_fields)]
+    struct Alt {
+        #[debug("short", alt = "long")]
+        x: u8,
+    }
+
+    assert_eq!(format!("{:?}", Alt { x: 1 }), "Alt { x: short }");
+    assert_eq!(format!("{:#?}", Alt { x: 1 }), "Alt {\n    x: long,\n}");
+}
+
+#[test]
+fn mode_only_fields() {
+    #[derive(Serialize, SmartDebug)]
+    struct Response {
+        status: u16,
+        #[debug(pretty_only, duration)]
+        elapsed: std::time::Duration,
+        #[debug(compact_only, "<{} bytes>")]
+        body_len: usize,
+        #[debug(pretty_only)]
+        body: &'static str,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    struct Tuple(u8, #[debug(pretty_only)] u8);
+
+    let response = Response {
+        status: 200,
+        elapsed: std::time::Duration::from_millis(42),
+        body_len: 2,
+        body: "ok",
+    };
+    let tuple = Tuple(1, 2);
+
+    insta::with_settings!({ info => &response }, {
+        insta::assert_snapshot!(format!("{response:?}"));
+        insta::assert_debug_snapshot!(response);
+    });
+    insta::with_settings!({ info => &tuple }, {
+        insta::assert_snapshot!(format!("{tuple:?} {tuple:#?}"));
+    });
+}
+
+#[test]
+fn exhaustive_markers() {
+    #[derive(Serialize, SmartDebug)]
+    #[debug(exhaustive_marker = count)]
+    struct Count {
+        ids: Vec<u32>,
+        #[debug(skip)]
+        cache: Vec<u8>,
+        #[debug(skip)]
+        handle: u8,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    #[debug(exhaustive_marker = names)]
+    struct Names {
+        id: u32,
+        #[debug(skip, rename = "cache_bytes")]
+        cache: Vec<u8>,
+        #[debug(skip_if = 0)]
+        handle: u8,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    #[debug(exhaustive_marker = false)]
+    struct NoMarker {
+        id: u32,
+        #[debug(skip)]
+        cache: Vec<u8>,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    #[non_exhaustive]
+    struct NonExhaustive {
+        id: u32,
+    }
+
+    let count = Count {
+        ids: vec![1, 2],
+        cache: vec![1, 2],
+        handle: 3,
+    };
+    let names = Names {
+        id: 2,
+        cache: vec![1, 2],
+        handle: 0,
+    };
+    let no_marker = NoMarker {
+        id: 3,
+        cache: vec![1, 2],
+    };
+    let non_exhaustive = NonExhaustive { id: 4 };
+
+    insta::with_settings!({ info => &(&count, &names, &no_marker, &non_exhaustive) }, {
+        insta::assert_snapshot!(format!(
+            "{count:?}\n{names:?}\n{no_marker:?}\n{non_exhaustive:?}"
+        ));
+        insta::assert_snapshot!(format!("{count:#?}\n{names:#?}"));
+    });
+
+    // Turning the marker off also applies to `#[non_exhaustive]` types
+    #[derive(SmartDebug)]
+    #[non_exhaustive]
+    #[debug(exhaustive_marker = false)]
+    struct Unmarked {
+        id: u32,
+    }
+
+    #[derive(SmartDebug)]
+    #[non_exhaustive]
+    #[debug(exhaustive_marker = false)]
+    struct UnmarkedTuple(u32);
+
+    assert_eq!(format!("{:?}", Unmarked { id: 5 }), "Unmarked { id: 5 }");
+    assert_eq!(format!("{:?}", UnmarkedTuple(5)), "UnmarkedTuple(5)");
+
+    // Pretty printing through the custom builders keeps the rest of the formatting options
+    #[derive(SmartDebug)]
+    #[debug(exhaustive_marker = count)]
+    struct Point {
+        x: f32,
+        #[debug(skip)]
+        y: f32,
+    }
+
+    #[derive(SmartDebug)]
+    #[non_exhaustive]
+    struct Scale(f32);
+
+    assert_eq!(
+        format!("{:#6.2?}", Point { x: 1.0, y: 2.0 }),
+        "Point {\n    x:   1.00,\n    .. (1 hidden)\n}",
+    );
+    assert_eq!(
+        format!("{:<+#6.1?}", Scale(0.5)),
+        "Scale(\n    +0.5  ,\n    ..\n)",
+    );
+}
+
+#[test]
+fn verbosity_levels() {
+    #[derive(Serialize, SmartDebug)]
+    struct Retry {
+        attempt: u8,
+        #[debug(level = 1)]
+        backoff_ms: u32,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    struct Conn {
+        peer: &'static str,
+        retry: Retry,
+        #[debug(level = 2)]
+        bytes_sent: u64,
+    }
+
+    let conn = Conn {
+        peer: "10.0.0.1",
+        retry: Retry {
+            attempt: 3,
+            backoff_ms: 250,
+        },
+        bytes_sent: 1024,
+    };
+
+    insta::with_settings!({ info => &conn }, {
+        insta::assert_snapshot!(format!(
+            "{:?}\n{:?}\n{:?}",
+            conn,
+            smart_debug::verbose(&conn, 1),
+            smart_debug::verbose(&conn, 2),
+        ));
+    });
+}
+
+#[test]
+fn named_views() {
+    use smart_debug::DebugViewExt;
+
+    enum Audit {}
+    enum Dev {}
+
+    #[derive(Serialize, SmartDebug)]
+    #[debug(view(Dev, inline_fields))]
+    struct Session {
+        #[debug(view(Audit, "<redacted>"))]
+        token: &'static str,
+        #[debug(skip, view(Dev, no_skip, lower_hex))]
+        flags: u8,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    #[debug(view(Dev))]
+    struct Request {
+        path: &'static str,
+        #[debug(view(Audit, skip))]
+        body: Vec<u8>,
+        session: Option<Session>,
+    }
+
+    let request = Request {
+        path: "/login",
+        body: vec![1, 2, 3],
+        session: Some(Session {
+            token: "hunter2",
+            flags: 0x2a,
+        }),
+    };
+
+    insta::with_settings!({ info => &request }, {
+        insta::assert_snapshot!(format!(
+            "{:?}\n{:?}\n{:#?}",
+            request,
+            request.debug_view::<Audit>(),
+            request.debug_view::<Dev>(),
+        ));
+    });
+}
+
+#[test]
+fn crate_path() {
+    mod facade {
+        pub use smart_debug;
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    #[debug(crate = facade::smart_debug, exhaustive_marker = count)]
+    struct Packet {
+        #[debug(hex)]
+        payload: Vec<u8>,
+        #[debug(level = 1)]
+        checksum: u32,
+        #[debug(skip)]
+        raw: Vec<u8>,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    #[debug(crate = facade::smart_debug, tuple_skip = omit)]
+    struct Pair(u8, #[debug(skip)] u8);
+
+    let packet = Packet {
+        payload: vec![0xde, 0xad],
+        checksum: 42,
+        raw: vec![1, 2, 3],
+    };
+    let pair = Pair(1, 2);
+
+    insta::with_settings!({ info => &(&packet, &pair) }, {
+        insta::assert_snapshot!(format!(
+            "{packet:?}\n{:?}\n{pair:?}",
+            smart_debug::verbose(&packet, 1),
+        ));
+    });
+}
+
+#[test]
+fn debug_fields() {
+    use smart_debug::{DebugFields, FieldVisitor};
+    use std::fmt;
+
+    /// Records every visited field as a `label = value` line
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl FieldVisitor for Recorder {
+        fn visit_field(&mut self, label: &str, value: &dyn fmt::Debug) {
+            self.0.push(format!("{label} = {value:?}"));
+        }
+
+        fn visit_skipped(&mut self, count: usize) {
+            self.0.push(format!("({count} skipped)"));
+        }
+    }
+
+    #[derive(Serialize, SmartDebug, Default)]
+    #[debug(skip_defaults)]
+    struct Named {
+        default_skipped: u8,
+        not_default: u8,
+        #[debug(skip)]
+        skipped: u8,
+        #[debug(rename = "renamed", "<redacted>")]
+        password: &'static str,
+    }
+
+    let named = Named {
+        not_default: 1,
+        skipped: 2,
+        password: "hunter2",
+        ..Default::default()
+    };
+    let mut recorder = Recorder::default();
+    named.visit(&mut recorder);
+
+    insta::with_settings!({ info => &named }, {
+        insta::assert_debug_snapshot!(recorder.0);
+    });
+
+    #[derive(Serialize, SmartDebug)]
+    struct Tuple(#[debug(skip)] (), #[debug(lower_hex)] u8);
+
+    let tuple = Tuple((), 255);
+    let mut recorder = Recorder::default();
+    tuple.visit(&mut recorder);
+
+    insta::with_settings!({ info => &tuple }, {
+        insta::assert_debug_snapshot!(recorder.0);
+    });
+
+    // Skipped fields don't have to be `Debug`
+    struct NotDebug;
+
+    #[derive(SmartDebug)]
+    struct Opaque {
+        id: u64,
+        #[debug(skip)]
+        inner: NotDebug,
+    }
+
+    let opaque = Opaque {
+        id: 1,
+        inner: NotDebug,
+    };
+    let mut recorder = Recorder::default();
+    opaque.visit(&mut recorder);
+    assert_eq!(recorder.0, ["id = 1", "(1 skipped)"]);
+}
+
+#[cfg(feature = "depth")]
+#[test]
+fn max_depth() {
+    #[derive(Serialize, SmartDebug)]
+    struct Expr {
+        op: &'static str,
+        args: Vec<Expr>,
+    }
+
+    #[derive(Serialize, SmartDebug)]
+    struct Pair(Expr, Expr);
+
+    fn expr(depth: u8) -> Expr {
+        let args = match depth {
+            0 => Vec::new(),
+            _ => vec![expr(depth - 1)],
+        };
+        Expr { op: "neg", args }
+    }
+
+    let deep = expr(4);
+    insta::with_settings!({ info => &deep }, {
+        insta::assert_snapshot!(format!("{:?}", smart_debug::depth_limited(&deep, 2)));
+        insta::assert_snapshot!(format!("{:#?}", smart_debug::depth_limited(&deep, 2)));
+    });
+
+    // Tuple structs get collapsed to `Name(..)` and the value itself can be collapsed too
+    let pair = Pair(expr(0), expr(1));
+    assert_eq!(
+        format!("{:?}", smart_debug::depth_limited(&pair, 2)),
+        r#"Pair(Expr { op: "neg", args: [] }, Expr { op: "neg", args: [Expr { .. }] })"#
+    );
+    assert_eq!(
+        format!("{:?}", smart_debug::depth_limited(&pair, 0)),
+        "Pair(..)"
+    );
+
+    // The container's limit applies to everything within it
+    #[derive(Serialize, SmartDebug)]
+    #[debug(max_depth = 3)]
+    struct Ast {
+        name: &'static str,
+        root: Expr,
+    }
+
+    let ast = Ast {
+        name: "deep",
+        root: expr(4),
+    };
+    insta::with_settings!({ info => &ast }, {
+        insta::assert_snapshot!(format!("{ast:?}"));
+    });
+    // Limits nest with the tighter one winning out
+    assert_eq!(
+        format!("{:?}", smart_debug::depth_limited(&ast, 1)),
+        r#"Ast { name: "deep", root: Expr { .. } }"#
+    );
+}
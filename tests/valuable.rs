@@ -0,0 +1,99 @@
+#![cfg(feature = "valuable")]
+
+use serde::Serialize;
+use smart_debug::SmartValuable;
+use valuable::{NamedValues, Valuable, Value, Visit};
+
+/// Records every visited field as a `label: value` line
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl Visit for Recorder {
+    fn visit_value(&mut self, value: Value<'_>) {
+        match value {
+            Value::Structable(structable) => {
+                self.0.push(format!("{}:", structable.definition().name()));
+                structable.visit(self);
+            }
+            other => self.0.push(format!("{other:?}")),
+        }
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        for (field, value) in named_values {
+            self.0.push(format!("{}: {value:?}", field.name()));
+        }
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        for value in values {
+            self.0.push(format!("{value:?}"));
+        }
+    }
+}
+
+fn record(value: &impl Valuable) -> Vec<String> {
+    let mut recorder = Recorder::default();
+    valuable::visit(value, &mut recorder);
+    recorder.0
+}
+
+#[test]
+fn named() {
+    #[derive(Serialize, SmartValuable, Default)]
+    #[debug(skip_defaults)]
+    struct Named {
+        default_skipped: u8,
+        not_default: u8,
+        #[debug(skip)]
+        skipped: &'static str,
+        #[debug(skip_if = 0)]
+        skip_if: i32,
+        #[debug(rename = "renamed")]
+        original: bool,
+        #[debug("<redacted>")]
+        password: &'static str,
+        #[debug("{:#x}")]
+        formatted: u32,
+    }
+
+    let named = Named {
+        not_default: 1,
+        skipped: "hidden",
+        original: true,
+        password: "hunter2",
+        formatted: 255,
+        ..Default::default()
+    };
+
+    insta::with_settings!({ info => &named }, {
+        insta::assert_debug_snapshot!(record(&named));
+    });
+}
+
+#[test]
+fn tuple() {
+    #[derive(Serialize, SmartValuable)]
+    struct Tuple(u8, #[debug(skip)] u8, #[debug(hex)] Vec<u8>);
+
+    let tuple = Tuple(1, 2, vec![0xab, 0xcd]);
+
+    insta::with_settings!({ info => &tuple }, {
+        insta::assert_debug_snapshot!(record(&tuple));
+    });
+}
+
+#[test]
+fn container_bare() {
+    #[derive(Serialize, SmartValuable)]
+    #[debug("<opaque>")]
+    struct Opaque {
+        secret: u64,
+    }
+
+    let opaque = Opaque { secret: 1234 };
+
+    insta::with_settings!({ info => &opaque }, {
+        insta::assert_debug_snapshot!(record(&opaque));
+    });
+}